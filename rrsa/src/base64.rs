@@ -0,0 +1,129 @@
+use alloc::{string::String, vec::Vec};
+use core::{error::Error, fmt::Display};
+
+
+/// Alphabet base64 standard (RFC 4648, section 4).
+pub const B64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+/// Alphabet base64url (RFC 4648, section 5), utilisable dans une URL : `-` et `_` remplacent `+` et `/`.
+pub const B64URL_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+/// Caractère de remplissage complétant le dernier groupe de 4 caractères.
+const B64_PAD: u8 = b'=';
+
+
+/// Erreurs pouvant survenir lors du décodage d'une chaîne base64.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Base64Error
+{
+    /// La chaîne contient un caractère hors de l'alphabet : il est donné.
+    InvalidCharacter(char),
+    /// Le nombre de caractères est impossible en base64 (longueur non multiple de 4 avec remplissage, ou d'un caractère de trop sans).
+    InvalidLength,
+    /// Le remplissage `=` est absent, en excès, placé ailleurs qu'en fin de chaîne, ou les bits inutilisés du dernier caractère ne sont pas nuls.
+    InvalidPadding
+}
+
+impl Display for Base64Error
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result
+    {
+        match self
+        {
+            Base64Error::InvalidCharacter(c) => write!(f, "Base64 invalide (Base64Error) : caractère `{}` hors de l'alphabet", c),
+            Base64Error::InvalidLength => write!(f, "Base64 invalide (Base64Error) : longueur incorrecte"),
+            Base64Error::InvalidPadding => write!(f, "Base64 invalide (Base64Error) : remplissage incorrect")
+        }
+    }
+}
+
+impl Error for Base64Error {}
+
+
+/// Encode des octets en base64 standard, complété par des `=` jusqu'à un multiple de 4 caractères.
+pub fn base64_encode(data: &[u8]) -> String
+{
+    encode_with(data, B64_ALPHABET, true)
+}
+
+/// Décode une chaîne base64 standard avec remplissage, telle que produite par `base64_encode`.
+/// Retourne une erreur si elle contient un caractère hors de l'alphabet ou si son remplissage est incorrect.
+pub fn base64_decode(s: &str) -> Result<Vec<u8>, Base64Error>
+{
+    decode_with(s, B64_ALPHABET, true)
+}
+
+/// Encode des octets en base64 avec l'alphabet `alphabet`, complété par des `=` si `pad` est vrai.
+pub fn encode_with(data: &[u8], alphabet: &[u8; 64], pad: bool) -> String
+{
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3)
+    {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let v = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        for i in 0..4
+        {
+            if i <= chunk.len()
+            {
+                out.push(alphabet[((v >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            }
+            else if pad
+            {
+                out.push(B64_PAD as char);
+            }
+        }
+    }
+
+    out
+}
+
+/// Décode une chaîne base64 écrite avec l'alphabet `alphabet`. Si `pad` est vrai, le remplissage est obligatoire (longueur multiple de 4) ;
+/// sinon, il est interdit. Voir `Base64Error` pour les cas de rejet.
+pub fn decode_with(s: &str, alphabet: &[u8; 64], pad: bool) -> Result<Vec<u8>, Base64Error>
+{
+    let data_len = s.trim_end_matches(B64_PAD as char).len();
+    let pad_len = s.len() - data_len;
+
+    if pad && !s.len().is_multiple_of(4)
+    {
+        return Err(Base64Error::InvalidLength);
+    }
+    if (pad && pad_len > 2) || (!pad && pad_len > 0)
+    {
+        return Err(Base64Error::InvalidPadding);
+    }
+    // Un caractère isolé ne porte que 6 bits : il ne peut terminer un encodage
+    if data_len % 4 == 1
+    {
+        return Err(Base64Error::InvalidLength);
+    }
+
+    let mut out = Vec::with_capacity(data_len * 3 / 4);
+    let (mut acc, mut nbits) = (0u32, 0u32);
+
+    for c in s[..data_len].chars()
+    {
+        let v = match alphabet.iter().position(| &a | char::from(a) == c)
+        {
+            Some(v) => v as u32,
+            None if c == B64_PAD as char => return Err(Base64Error::InvalidPadding),
+            None => return Err(Base64Error::InvalidCharacter(c))
+        };
+        acc = (acc << 6) | v;
+        nbits += 6;
+        if nbits >= 8
+        {
+            nbits -= 8;
+            out.push((acc >> nbits) as u8);
+            acc &= (1 << nbits) - 1;
+        }
+    }
+
+    // Les bits restants ne complètent aucun octet : un encodage canonique les laisse à zéro
+    if acc != 0
+    {
+        return Err(Base64Error::InvalidPadding);
+    }
+
+    Ok(out)
+}
\ No newline at end of file
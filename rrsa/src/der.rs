@@ -0,0 +1,183 @@
+use num_bigint::BigUint;
+use alloc::{vec, vec::Vec};
+use core::{error::Error, fmt::Display};
+
+
+/// Tag DER d'un entier.
+pub const DER_INTEGER: u8 = 0x02;
+/// Tag DER d'une chaîne d'octets.
+pub const DER_OCTET_STRING: u8 = 0x04;
+/// Tag DER de la valeur nulle.
+pub const DER_NULL: u8 = 0x05;
+/// Tag DER d'un identifiant d'objet (OID).
+pub const DER_OID: u8 = 0x06;
+/// Tag DER d'une séquence.
+pub const DER_SEQUENCE: u8 = 0x30;
+
+
+/// Erreurs pouvant survenir lors du décodage d'une structure DER.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DerError
+{
+    /// Les données s'arrêtent avant la fin de l'élément.
+    Truncated,
+    /// Le tag lu n'est pas celui attendu.
+    UnexpectedTag(u8),
+    /// L'encodage de la longueur est invalide ou non supporté.
+    InvalidLength,
+    /// Le contenu d'un entier est vide ou négatif (bit de poids fort à 1).
+    InvalidInteger
+}
+
+impl Display for DerError
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result
+    {
+        match self
+        {
+            DerError::Truncated => write!(f, "Erreur DER (DerError) : données tronquées"),
+            DerError::UnexpectedTag(tag) => write!(f, "Erreur DER (DerError) : tag inattendu 0x{:02x}", tag),
+            DerError::InvalidLength => write!(f, "Erreur DER (DerError) : longueur invalide"),
+            DerError::InvalidInteger => write!(f, "Erreur DER (DerError) : entier invalide")
+        }
+    }
+}
+
+impl Error for DerError {}
+
+
+/// Encode la longueur d'un élément DER (forme courte sous 128 octets, forme longue sinon).
+fn encode_length(len: usize) -> Vec<u8>
+{
+    if len < 0x80
+    {
+        return vec![len as u8];
+    }
+
+    let bytes: Vec<u8> = len.to_be_bytes().iter().copied().skip_while(| &b | b == 0).collect();
+    let mut out = vec![0x80 | bytes.len() as u8];
+    out.extend(bytes);
+
+    out
+}
+
+/// Encode un élément DER complet (tag, longueur, contenu).
+fn encode_tlv(tag: u8, content: &[u8]) -> Vec<u8>
+{
+    let mut out = vec![tag];
+    out.extend(encode_length(content.len()));
+    out.extend_from_slice(content);
+
+    out
+}
+
+/// Encode un grand entier positif en INTEGER DER ; un octet nul est ajouté en tête si le bit de poids fort est à 1.
+pub fn encode_integer(n: &BigUint) -> Vec<u8>
+{
+    let mut content = n.to_bytes_be();
+    if content[0] & 0x80 != 0
+    {
+        content.insert(0, 0x00);
+    }
+
+    encode_tlv(DER_INTEGER, &content)
+}
+
+/// Encode une SEQUENCE DER à partir de ses éléments déjà encodés.
+pub fn encode_sequence(parts: &[Vec<u8>]) -> Vec<u8>
+{
+    encode_tlv(DER_SEQUENCE, &parts.concat())
+}
+
+/// Encode une OCTET STRING DER.
+pub fn encode_octet_string(data: &[u8]) -> Vec<u8>
+{
+    encode_tlv(DER_OCTET_STRING, data)
+}
+
+/// Encode un OBJECT IDENTIFIER DER à partir de son contenu déjà encodé (par exemple `hash::SHA256_OID`).
+pub fn encode_oid(oid: &[u8]) -> Vec<u8>
+{
+    encode_tlv(DER_OID, oid)
+}
+
+/// Encode la valeur NULL DER.
+pub fn encode_null() -> Vec<u8>
+{
+    encode_tlv(DER_NULL, &[])
+}
+
+/// Lit un élément DER dont le tag doit être `tag` ; retourne son contenu et le nombre total d'octets consommés.
+fn decode_tlv(bytes: &[u8], tag: u8) -> Result<(&[u8], usize), DerError>
+{
+    let found = *bytes.first().ok_or(DerError::Truncated)?;
+    if found != tag
+    {
+        return Err(DerError::UnexpectedTag(found));
+    }
+
+    let first = *bytes.get(1).ok_or(DerError::Truncated)?;
+    let (len, hlen) = if first < 0x80
+    {
+        (usize::from(first), 2)
+    }
+    else
+    {
+        let nlen = usize::from(first & 0x7f);
+        if nlen == 0 || nlen > core::mem::size_of::<usize>()
+        {
+            return Err(DerError::InvalidLength);
+        }
+        let len = bytes.get(2..(2 + nlen)).ok_or(DerError::Truncated)?.iter().fold(0usize, | acc, &b | (acc << 8) | usize::from(b));
+        (len, 2 + nlen)
+    };
+
+    let end = hlen.checked_add(len).ok_or(DerError::InvalidLength)?;
+    let content = bytes.get(hlen..end).ok_or(DerError::Truncated)?;
+
+    Ok((content, end))
+}
+
+/// Décode un INTEGER DER positif en tête de `bytes` ; retourne sa valeur et le nombre d'octets consommés.
+pub fn decode_integer(bytes: &[u8]) -> Result<(BigUint, usize), DerError>
+{
+    let (content, used) = decode_tlv(bytes, DER_INTEGER)?;
+    if content.is_empty() || content[0] & 0x80 != 0
+    {
+        return Err(DerError::InvalidInteger);
+    }
+
+    Ok((BigUint::from_bytes_be(content), used))
+}
+
+/// Décode une OCTET STRING DER en tête de `bytes` ; retourne son contenu et le nombre d'octets consommés.
+pub fn decode_octet_string(bytes: &[u8]) -> Result<(&[u8], usize), DerError>
+{
+    decode_tlv(bytes, DER_OCTET_STRING)
+}
+
+/// Décode l'en-tête d'une SEQUENCE DER en tête de `bytes` ; retourne son contenu et le nombre d'octets consommés.
+pub fn decode_sequence(bytes: &[u8]) -> Result<(&[u8], usize), DerError>
+{
+    decode_tlv(bytes, DER_SEQUENCE)
+}
+
+/// Décode une SEQUENCE DER ne contenant que des INTEGER et retourne leurs valeurs. La séquence doit occuper tout `bytes`.
+pub fn decode_integer_sequence(bytes: &[u8]) -> Result<Vec<BigUint>, DerError>
+{
+    let (mut rest, used) = decode_sequence(bytes)?;
+    if used != bytes.len()
+    {
+        return Err(DerError::InvalidLength);
+    }
+
+    let mut ints = Vec::new();
+    while !rest.is_empty()
+    {
+        let (n, used) = decode_integer(rest)?;
+        ints.push(n);
+        rest = &rest[used..];
+    }
+
+    Ok(ints)
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+use crate::{maths, zeroize::Zeroize};
+use num_bigint::{BigUint, RandBigInt, ToBigInt};
+use rand::{CryptoRng, Rng};
+
+
+/// Paire de clés ElGamal sur le groupe multiplicatif modulo un nombre premier `p`.
+/// Le schéma repose entièrement sur le module `maths` : génération du nombre premier (`maths::rand_safe_prime`),
+/// du générateur (`maths::primitive_root`), exponentiation (`maths::fmodpow`) et inverse modulaire (`maths::mod_inverse`).
+pub struct KeyPair
+{
+    /// Nombre premier définissant le groupe.
+    pub p: BigUint,
+    /// Générateur du groupe multiplicatif modulo `p` (racine primitive).
+    pub g: BigUint,
+    /// Clé publique, `g^x mod p`.
+    pub y: BigUint,
+    /// Clé privée, tirée dans `[1, p - 2]`.
+    pub x: BigUint
+}
+
+/// La clé privée est effacée (voir `zeroize::Zeroize`) avant la libération de la clé.
+impl Drop for KeyPair
+{
+    fn drop(&mut self)
+    {
+        self.x.zeroize();
+    }
+}
+
+impl KeyPair
+{
+    /// Génère une clé ElGamal dont le nombre premier est un nombre premier sûr de `szb` octets (la factorisation de `p - 1`
+    /// nécessaire à la recherche du générateur est alors immédiate), tiré depuis `rng`. Cette génération est lente pour de grandes tailles.
+    pub fn generate<R: Rng + CryptoRng>(rng: &mut R, szb: u64) -> KeyPair
+    {
+        let p = maths::rand_safe_prime(rng, szb);
+
+        // p étant premier, la racine primitive existe toujours
+        Self::from_prime(p, rng).expect("elgamal.KeyPair.generate")
+    }
+
+    /// Construit une clé ElGamal sur le nombre premier `p` donné : le générateur est sa plus petite racine primitive,
+    /// et la clé privée est tirée depuis `rng`. Retourne `None` si `p` n'est pas premier ou est inférieur à 5.
+    pub fn from_prime<R: Rng + CryptoRng>(p: BigUint, rng: &mut R) -> Option<KeyPair>
+    {
+        if p < BigUint::from(5u8)
+        {
+            return None;
+        }
+
+        let g = maths::primitive_root(&p)?;
+        let x = rng.gen_biguint_range(&BigUint::from(1u8), &(&p - 1u8));
+        let y = maths::fmodpow(&g, &x, &p);
+
+        Some(KeyPair { p, g, y, x })
+    }
+
+    /// Chiffre `msg`, qui doit être strictement inférieur à `p` : pour un aléa éphémère `k` tiré depuis `rng`,
+    /// retourne `(c1, c2) = (g^k mod p, msg * y^k mod p)`. Deux chiffrements d'un même message diffèrent donc.
+    /// Panique si `msg` n'est pas inférieur à `p`.
+    pub fn encrypt(&self, msg: &BigUint, rng: &mut impl Rng) -> (BigUint, BigUint)
+    {
+        assert!(msg < &self.p, "elgamal.KeyPair.encrypt : message supérieur ou égal au nombre premier");
+
+        let k = rng.gen_biguint_range(&BigUint::from(1u8), &(&self.p - 1u8));
+        let c1 = maths::fmodpow(&self.g, &k, &self.p);
+        let c2 = maths::mod_mul(msg, &maths::fmodpow(&self.y, &k, &self.p), &self.p);
+
+        (c1, c2)
+    }
+
+    /// Déchiffre un couple produit par `encrypt` : `msg = c2 * (c1^x)^-1 mod p`.
+    /// Retourne `None` si `c1` n'est pas inversible modulo `p` (c'est-à-dire nul), ce qui ne se produit pas pour un chiffré valide.
+    pub fn decrypt(&self, (c1, c2): (&BigUint, &BigUint)) -> Option<BigUint>
+    {
+        let s = maths::fmodpow(c1, &self.x, &self.p);
+        let s_inv = maths::mod_inverse(&s.to_bigint().unwrap(), &self.p.to_bigint().unwrap())?;
+
+        Some(maths::mod_mul(c2, &s_inv.to_biguint().unwrap(), &self.p))
+    }
+}
\ No newline at end of file
@@ -1,8 +1,7 @@
 use crate::{keys::*, maths, messages::Message};
-use std::{convert::TryInto, sync::{Arc, atomic}, thread};
+use std::{convert::TryInto, sync::{Arc, atomic}, thread, time::Duration};
 use crossbeam::channel;
 use num_bigint::{BigUint, RandBigInt, ToBigInt};
-use num_traits::Signed;
 
 
 /// Taille par défaut du padding (nonce) à appliquer aux nombres à chiffrer. Peut changer en fonction du message. En octets.
@@ -122,6 +121,11 @@ pub const RSA_DEF_PRIME_SIZEB: u64 = 128;
 /// Le nombre par défaut de 4 correspond aux processeurs modernes dont la plupart ont quatre coeurs.
 /// Ce nombre n'est utilisé qu'en cas d'échec de l'obtention du nombre de coeurs du processeur de la machine locale ; sinon ce dernier est utilisé (par défaut) afin de paralléliser au maximum la génération.
 pub const RSA_DEF_GEN_THREADS: u8 = 4;
+/// Nombre de candidats en attente de vérification autorisés par thread lors de la génération RSA.
+/// Le générateur étant bien plus rapide que les tests de primalité, une file non bornée grossirait indéfiniment.
+const RSA_GEN_QUEUE_PER_THREAD: usize = 64;
+/// Délai d'attente maximal du générateur lorsque la file des candidats est pleine, avant de revérifier si la génération est terminée.
+const RSA_GEN_SEND_TIMEOUT: Duration = Duration::from_millis(10);
 
 /// Alias de type pour les clés de chiffrement RSA, qui sont des paires de clés numériques (n, e).
 pub type PublicKey = KeyPair<NumKey, NumKey>;
@@ -141,21 +145,52 @@ impl Rsa
     {
         num_cpus::get().try_into().unwrap_or(RSA_DEF_GEN_THREADS)
     } 
-}
 
-impl Engine for Rsa
-{
-    type EncryptionKey = PublicKey;
-    type DecryptionKey = PrivateKey;
-    type MainKey = RsaKey;
+    /// Génère une clé RSA (voir `Engine::generate`) en effectuant `rounds` itérations du test de Miller-Rabin sur chaque candidat.
+    /// Avec `None`, le nombre d'itérations par défaut (20) est utilisé.
+    /// Si aucun exposant de la table de `maths::expcode` ne convient aux nombres premiers tirés (voir `key_from_primes`),
+    /// deux nouveaux nombres premiers sont tirés : la génération aboutit toujours à une clé valide.
+    pub fn generate_rounds(&self, sz_b: u64, n_threads: u8, rounds: Option<u32>) -> RsaKey
+    {
+        let rounds = rounds.unwrap_or(maths::PRIME_ROUNDS_DEF);
 
-    fn generate(&self, sz_b: u64, n_threads: u8) -> Self::MainKey 
+        loop
+        {
+            let (p, q) = self.draw_primes(sz_b, n_threads, rounds);
+            if let Some(key) = Self::key_from_primes(p, q)
+            {
+                return key;
+            }
+        }
+    }
+
+    /// Construit la clé RSA des nombres premiers `p` et `q` : l'exposant public est donné par `maths::expcode` de l'indicatrice
+    /// (le plus petit nombre premier de sa table ne la divisant pas). Retourne `None` si tous les nombres premiers de la table
+    /// divisent l'indicatrice, aucun exposant n'étant alors disponible.
+    pub(crate) fn key_from_primes(p: BigUint, q: BigUint) -> Option<RsaKey>
+    {
+        let n = &p * &q;
+        let ind = (p - 1u8) * (q - 1u8);
+        let e = maths::expcode(&ind)?;
+        // e est premier et ne divise pas l'indicatrice : l'inverse existe
+        let d = maths::mod_inverse(&e.to_bigint().unwrap(), &ind.to_bigint().unwrap()).unwrap();
+
+        Some(KeyPair::from(
+            KeyPair::from(NumKey::from(n.clone()), NumKey::from(e)), 
+            KeyPair::from(NumKey::from(n), NumKey::from(d.to_biguint().unwrap()))
+        ))
+    }
+
+    /// Tire deux nombres premiers de `sz_b` octets, les candidats étant testés sur `n_threads` threads avec `rounds` itérations.
+    fn draw_primes(&self, sz_b: u64, n_threads: u8, rounds: u32) -> (BigUint, BigUint)
     {
-        // g_xx est un canal permettant de passer les entiers dont la primalité est à tester
-        let (g_tx, g_rx) = channel::unbounded();
+        // g_xx est un canal (borné) permettant de passer les entiers dont la primalité est à tester
+        let (g_tx, g_rx) = channel::bounded(usize::from(n_threads.max(1)) * RSA_GEN_QUEUE_PER_THREAD);
         // f_xx est un canal permettant de passer des nombres premiers satisfaisant toutes les contraintes
         let (f_tx, f_rx) = channel::unbounded();
         let working = Arc::new(atomic::AtomicBool::new(true));
+        // Petits nombres premiers permettant d'écarter la plupart des candidats sans test de Miller-Rabin
+        let sieve = Arc::new(maths::small_prime_sieve(maths::SIEVE_LIMIT_DEF));
         
         for _ in 0..n_threads
         {
@@ -163,15 +198,20 @@ impl Engine for Rsa
             let f_tx_c = f_tx.clone();
             let f_rx_c = f_rx.clone();
             let working_f_c = working.clone();
+            let sieve_c = sieve.clone();
 
             thread::spawn(move || {
                 let mut temp;
                 while working_f_c.load(atomic::Ordering::Relaxed)
                 {
-                    // On prend un nombre premier généré
-                    temp = g_rx_c.recv().unwrap();
-                    // On fait le test de primalité
-                    if maths::isprime(&temp)
+                    // On prend un nombre premier généré ; si le générateur a terminé, il n'y a plus rien à vérifier
+                    temp = match g_rx_c.recv()
+                    {
+                        Ok(temp) => temp,
+                        Err(_) => break
+                    };
+                    // On fait le test de primalité, après avoir écarté les candidats ayant un petit facteur
+                    if !maths::divisible_by_small(&temp, &sieve_c) && maths::is_prime_rounds(&temp, rounds)
                     {
                         // S'il est premier on l'envoie dans le canal de sortie
                         f_tx_c.send(temp).expect("Rsa.generate : erreur dans le remplissage.");
@@ -189,25 +229,27 @@ impl Engine for Rsa
         // Pas besoin de join les threads
         while working.load(atomic::Ordering::Relaxed)
         {
-            // On remplit le canal avec des entiers suscesptibles d'être premiers
-            g_tx.send(maths::rand_primelike(sz_b)).expect("Rsa.generate : erreur dans la génération.");
+            // On remplit le canal avec des entiers suscesptibles d'être premiers ; si le canal reste plein, le candidat est abandonné
+            // et l'on revérifie si la génération est terminée
+            if let Err(channel::SendTimeoutError::Disconnected(_)) = g_tx.send_timeout(maths::rand_primelike_def(sz_b), RSA_GEN_SEND_TIMEOUT)
+            {
+                panic!("Rsa.generate : erreur dans la génération.");
+            }
         }
 
-        let (p, q) = (f_rx.recv().unwrap(), f_rx.recv().unwrap());
-        let n = &p * &q;
-        let ind = (p - 1u8) * (q - 1u8);
-        let e = maths::expcode(&ind).unwrap();
-        let mut d = maths::euclide(&e.to_bigint().unwrap(), &ind.to_bigint().unwrap());
+        (f_rx.recv().unwrap(), f_rx.recv().unwrap())
+    }
+}
 
-        if d.is_negative()
-        {
-            d += ind.to_bigint().unwrap();
-        }
+impl Engine for Rsa
+{
+    type EncryptionKey = PublicKey;
+    type DecryptionKey = PrivateKey;
+    type MainKey = RsaKey;
 
-        KeyPair::from(
-            KeyPair::from(NumKey::from(n.clone()), NumKey::from(e)), 
-            KeyPair::from(NumKey::from(n), NumKey::from(d.to_biguint().unwrap()))
-        )
+    fn generate(&self, sz_b: u64, n_threads: u8) -> Self::MainKey 
+    {
+        self.generate_rounds(sz_b, n_threads, None)
     }
 
     fn gen_def(&self) -> Self::MainKey 
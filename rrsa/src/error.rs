@@ -0,0 +1,113 @@
+use crate::{base64::Base64Error, der::DerError, maths::MathError, pkcs1::PadError};
+#[cfg(feature = "json")]
+use crate::json::JsonError;
+#[cfg(feature = "std")]
+use crate::{pem::PemError, rsa::KeyError};
+use core::{error::Error as StdError, fmt::Display};
+
+
+/// Erreur commune à l'ensemble de la bibliothèque, regroupant les erreurs de chaque module : une fonction combinant plusieurs modules
+/// peut ainsi propager leurs erreurs avec `?`. Les modules propres à `std` (clés RSA, PEM) ne sont présents qu'avec cette fonctionnalité,
+/// et celui des clés JSON qu'avec la fonctionnalité `json`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error
+{
+    /// Erreur des opérations sur les grands entiers (voir `maths::MathError`).
+    Math(MathError),
+    /// Erreur d'encodage DER (voir `der::DerError`).
+    Der(DerError),
+    /// Erreur de padding PKCS#1 (voir `pkcs1::PadError`).
+    Pad(PadError),
+    /// Erreur de décodage base64 (voir `base64::Base64Error`).
+    Base64(Base64Error),
+    /// Clé RSA invalide ou génération impossible (voir `rsa::KeyError`).
+    #[cfg(feature = "std")]
+    Key(KeyError),
+    /// Erreur de lecture d'une clé PEM (voir `pem::PemError`).
+    #[cfg(feature = "std")]
+    Pem(PemError),
+    /// Erreur de lecture d'une clé JSON (voir `json::JsonError`).
+    #[cfg(feature = "json")]
+    Json(JsonError)
+}
+
+impl Display for Error
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result
+    {
+        match self
+        {
+            Error::Math(e) => write!(f, "Erreur RRSA (Error) : {}", e),
+            Error::Der(e) => write!(f, "Erreur RRSA (Error) : {}", e),
+            Error::Pad(e) => write!(f, "Erreur RRSA (Error) : {}", e),
+            Error::Base64(e) => write!(f, "Erreur RRSA (Error) : {}", e),
+            #[cfg(feature = "std")]
+            Error::Key(e) => write!(f, "Erreur RRSA (Error) : {}", e),
+            #[cfg(feature = "std")]
+            Error::Pem(e) => write!(f, "Erreur RRSA (Error) : {}", e),
+            #[cfg(feature = "json")]
+            Error::Json(e) => write!(f, "Erreur RRSA (Error) : {}", e)
+        }
+    }
+}
+
+impl StdError for Error {}
+
+impl From<MathError> for Error
+{
+    fn from(e: MathError) -> Self
+    {
+        Error::Math(e)
+    }
+}
+
+impl From<DerError> for Error
+{
+    fn from(e: DerError) -> Self
+    {
+        Error::Der(e)
+    }
+}
+
+impl From<PadError> for Error
+{
+    fn from(e: PadError) -> Self
+    {
+        Error::Pad(e)
+    }
+}
+
+impl From<Base64Error> for Error
+{
+    fn from(e: Base64Error) -> Self
+    {
+        Error::Base64(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<KeyError> for Error
+{
+    fn from(e: KeyError) -> Self
+    {
+        Error::Key(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<PemError> for Error
+{
+    fn from(e: PemError) -> Self
+    {
+        Error::Pem(e)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<JsonError> for Error
+{
+    fn from(e: JsonError) -> Self
+    {
+        Error::Json(e)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,209 @@
+use alloc::vec::Vec;
+
+
+/// Algorithme de hachage utilisable pour les signatures PKCS#1 v1.5 (voir `rsa::KeyPair::sign`) : fournit l'empreinte
+/// et l'OID inscrit dans la structure DigestInfo.
+pub trait DigestAlgorithm
+{
+    /// Contenu DER de l'OID de l'algorithme.
+    fn oid(&self) -> &'static [u8];
+    /// Calcule l'empreinte de `data`.
+    fn digest(&self, data: &[u8]) -> Vec<u8>;
+}
+
+/// SHA-256, toujours disponible (également utilisé par MGF1, OAEP et PSS).
+pub struct Sha256;
+
+impl DigestAlgorithm for Sha256
+{
+    fn oid(&self) -> &'static [u8]
+    {
+        SHA256_OID
+    }
+
+    fn digest(&self, data: &[u8]) -> Vec<u8>
+    {
+        sha256(data)
+    }
+}
+
+/// SHA-512, disponible avec la fonctionnalité `sha2` (activée par défaut).
+#[cfg(feature = "sha2")]
+pub struct Sha512;
+
+#[cfg(feature = "sha2")]
+impl DigestAlgorithm for Sha512
+{
+    fn oid(&self) -> &'static [u8]
+    {
+        SHA512_OID
+    }
+
+    fn digest(&self, data: &[u8]) -> Vec<u8>
+    {
+        sha512(data)
+    }
+}
+
+
+/// Constantes de tour de SHA-256 (parties fractionnaires des racines cubiques des 64 premiers nombres premiers).
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2
+];
+/// Valeurs initiales de l'état SHA-256.
+const SHA256_H0: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+/// Taille en octets d'une empreinte SHA-256.
+pub const SHA256_LEN: usize = 32;
+/// Contenu DER de l'OID de SHA-256 (2.16.840.1.101.3.4.2.1), utilisé dans la structure DigestInfo des signatures PKCS#1 v1.5.
+pub const SHA256_OID: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+
+
+/// Calcule l'empreinte SHA-256 (FIPS 180-4) de `data`.
+pub fn sha256(data: &[u8]) -> Vec<u8>
+{
+    let mut h = SHA256_H0;
+
+    // Remplissage : un bit à 1, des zéros, puis la longueur en bits sur 64 bits, pour obtenir un multiple de 64 octets
+    let mut msg = data.to_vec();
+    msg.push(0x80);
+    while msg.len() % 64 != 56
+    {
+        msg.push(0x00);
+    }
+    msg.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_be_bytes());
+
+    for chunk in msg.chunks(64)
+    {
+        let mut w = [0u32; 64];
+        for (i, word) in chunk.chunks(4).enumerate()
+        {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64
+        {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for i in 0..64
+        {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = hh.wrapping_add(s1).wrapping_add(ch).wrapping_add(SHA256_K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (state, v) in h.iter_mut().zip([a, b, c, d, e, f, g, hh].iter())
+        {
+            *state = state.wrapping_add(*v);
+        }
+    }
+
+    h.iter().flat_map(| word | word.to_be_bytes()).collect()
+}
+
+
+/// Constantes de tour de SHA-512 (parties fractionnaires des racines cubiques des 80 premiers nombres premiers).
+#[cfg(feature = "sha2")]
+const SHA512_K: [u64; 80] = [
+    0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc, 0x3956c25bf348b538, 0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118,
+    0xd807aa98a3030242, 0x12835b0145706fbe, 0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2, 0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235, 0xc19bf174cf692694,
+    0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65, 0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5,
+    0x983e5152ee66dfab, 0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4, 0xc6e00bf33da88fc2, 0xd5a79147930aa725, 0x06ca6351e003826f, 0x142929670a0e6e70,
+    0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed, 0x53380d139d95b3df, 0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+    0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30, 0xd192e819d6ef5218, 0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8, 0x1e376c085141ab53, 0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8, 0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373, 0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec, 0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b,
+    0xca273eceea26619c, 0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178, 0x06f067aa72176fba, 0x0a637dc5a2c898a6, 0x113f9804bef90dae, 0x1b710b35131c471b,
+    0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc, 0x431d67c49c100d4c, 0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817
+];
+/// Valeurs initiales de l'état SHA-512.
+#[cfg(feature = "sha2")]
+const SHA512_H0: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1, 0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179
+];
+/// Taille en octets d'une empreinte SHA-512.
+#[cfg(feature = "sha2")]
+pub const SHA512_LEN: usize = 64;
+/// Contenu DER de l'OID de SHA-512 (2.16.840.1.101.3.4.2.3).
+#[cfg(feature = "sha2")]
+pub const SHA512_OID: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03];
+
+
+/// Calcule l'empreinte SHA-512 (FIPS 180-4) de `data`. Même construction que SHA-256, sur des mots de 64 bits et des blocs de 128 octets.
+#[cfg(feature = "sha2")]
+pub fn sha512(data: &[u8]) -> Vec<u8>
+{
+    let mut h = SHA512_H0;
+
+    // Remplissage : un bit à 1, des zéros, puis la longueur en bits sur 128 bits, pour obtenir un multiple de 128 octets
+    let mut msg = data.to_vec();
+    msg.push(0x80);
+    while msg.len() % 128 != 112
+    {
+        msg.push(0x00);
+    }
+    msg.extend_from_slice(&((data.len() as u128).wrapping_mul(8)).to_be_bytes());
+
+    for chunk in msg.chunks(128)
+    {
+        let mut w = [0u64; 80];
+        for (i, word) in chunk.chunks(8).enumerate()
+        {
+            w[i] = u64::from_be_bytes([word[0], word[1], word[2], word[3], word[4], word[5], word[6], word[7]]);
+        }
+        for i in 16..80
+        {
+            let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+            let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for i in 0..80
+        {
+            let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = hh.wrapping_add(s1).wrapping_add(ch).wrapping_add(SHA512_K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (state, v) in h.iter_mut().zip([a, b, c, d, e, f, g, hh].iter())
+        {
+            *state = state.wrapping_add(*v);
+        }
+    }
+
+    h.iter().flat_map(| word | word.to_be_bytes()).collect()
+}
\ No newline at end of file
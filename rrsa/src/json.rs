@@ -0,0 +1,249 @@
+//! Sérialisation des clés RSA en JSON (`KeyPair::to_json` / `from_json`) et en JWK (`PublicKey::to_jwk` / `from_jwk`),
+//! disponible avec la fonctionnalité `json`. Il s'agit de fonctions écrites à la main pour ces seuls formats, et non
+//! d'implémentations de `Serialize` / `Deserialize` de serde : la bibliothèque ne dépend pas de serde.
+
+use crate::{base64, rsa::{self, CrtParams, KeyError, KeyPair, PublicKey}};
+use num_bigint::BigUint;
+use num_traits::Num;
+use std::{error::Error, fmt::Display};
+
+
+/// Base utilisée pour les valeurs numériques des clés sérialisées en JSON (hexadécimal minuscule).
+const JSON_RADIX: u32 = 16;
+
+
+/// Erreurs pouvant survenir lors de la lecture d'une clé au format JSON.
+#[derive(Debug, PartialEq, Eq)]
+pub enum JsonError
+{
+    /// Le texte n'est pas un objet JSON plat de chaînes valide.
+    Syntax,
+    /// Un champ obligatoire est absent.
+    MissingField(&'static str),
+    /// La valeur d'un champ n'est pas un nombre hexadécimal valide.
+    InvalidHex(String),
+    /// L'exposant public est pair, ce qui est impossible pour une clé RSA.
+    EvenExponent,
+    /// Le module est inférieur à 256 et ne permet pas de chiffrer (voir `rsa::KeyError::ModulusTooSmall`).
+    ModulusTooSmall,
+    /// La valeur d'un champ d'une clé JWK n'est pas du base64url valide.
+    InvalidBase64(String),
+    /// Le type `kty` d'une clé JWK n'est pas `RSA`.
+    UnsupportedKeyType(String),
+    /// La clé privée lue est bien formée mais incohérente (voir `KeyPair::validate`).
+    InvalidKey(KeyError)
+}
+
+impl Display for JsonError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            JsonError::Syntax => write!(f, "Impossible de lire la clé JSON (JsonError) : syntaxe invalide"),
+            JsonError::MissingField(field) => write!(f, "Impossible de lire la clé JSON (JsonError) : champ `{}` absent", field),
+            JsonError::InvalidHex(field) => write!(f, "Impossible de lire la clé JSON (JsonError) : champ `{}` non hexadécimal", field),
+            JsonError::EvenExponent => write!(f, "Impossible de lire la clé JSON (JsonError) : exposant public pair"),
+            JsonError::ModulusTooSmall => write!(f, "Impossible de lire la clé JSON (JsonError) : module inférieur à 256"),
+            JsonError::InvalidBase64(field) => write!(f, "Impossible de lire la clé JSON (JsonError) : champ `{}` non base64url", field),
+            JsonError::UnsupportedKeyType(kty) => write!(f, "Impossible de lire la clé JSON (JsonError) : type de clé `{}` non RSA", kty),
+            JsonError::InvalidKey(e) => write!(f, "Impossible de lire la clé JSON (JsonError) : {}", e)
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+
+impl KeyPair
+{
+    /// Sérialise la clé en un objet JSON dont les valeurs sont des chaînes hexadécimales minuscules :
+    /// `n`, `e`, `d`, ainsi que `p`, `q`, `dp`, `dq`, `qinv` si les paramètres CRT sont présents.
+    /// Les paramètres CRT d'une clé multi-premiers ne sont pas sérialisés : seul `d` permet alors le déchiffrement.
+    pub fn to_json(&self) -> String
+    {
+        let mut fields = vec![("n", &self.n), ("e", &self.e), ("d", &self.d)];
+        if let Some(crt) = self.crt.as_ref().filter(| crt | crt.others.is_empty())
+        {
+            fields.extend_from_slice(&[("p", &crt.p), ("q", &crt.q), ("dp", &crt.dp), ("dq", &crt.dq), ("qinv", &crt.qinv)]);
+        }
+
+        let body: Vec<String> = fields.iter().map(| (name, value) | {
+            format!("\"{}\":\"{}\"", name, value.to_str_radix(JSON_RADIX))
+        }).collect();
+
+        format!("{{{}}}", body.join(","))
+    }
+
+    /// Désérialise une clé produite par `to_json`. Les valeurs doivent être hexadécimales, l'exposant public impair et le module
+    /// d'au moins 256. Les paramètres CRT ne sont renseignés que s'ils sont tous présents, et la clé obtenue est vérifiée par
+    /// `KeyPair::validate` (`JsonError::InvalidKey` si elle est incohérente, par exemple un `dp` corrompu).
+    pub fn from_json(json: &str) -> Result<Self, JsonError>
+    {
+        let fields = parse_flat_object(json)?;
+        let get = | name: &'static str | -> Result<Option<BigUint>, JsonError> {
+            match fields.iter().find(| (k, _) | k == name)
+            {
+                Some((_, v)) => BigUint::from_str_radix(v, JSON_RADIX).map(Some).map_err(| _ | JsonError::InvalidHex(String::from(name))),
+                None => Ok(None)
+            }
+        };
+        let require = | name: &'static str | get(name)?.ok_or(JsonError::MissingField(name));
+
+        let (n, e, d) = (require("n")?, require("e")?, require("d")?);
+        if !e.bit(0)
+        {
+            return Err(JsonError::EvenExponent);
+        }
+        if rsa::check_modulus(&n).is_err()
+        {
+            return Err(JsonError::ModulusTooSmall);
+        }
+
+        let crt = match (get("p")?, get("q")?, get("dp")?, get("dq")?, get("qinv")?)
+        {
+            (Some(p), Some(q), Some(dp), Some(dq), Some(qinv)) => Some(CrtParams { p, q, dp, dq, qinv, others: Vec::new() }),
+            _ => None
+        };
+
+        let key = KeyPair { n, e, d, crt };
+        key.validate().map_err(JsonError::InvalidKey)?;
+
+        Ok(key)
+    }
+}
+
+
+impl PublicKey
+{
+    /// Exporte la clé au format JWK (JSON Web Key, RFC 7517 / 7518) : `{"kty":"RSA","n":"...","e":"..."}`,
+    /// les valeurs étant les octets big-endian des nombres en base64url sans remplissage.
+    pub fn to_jwk(&self) -> String
+    {
+        jwk_object(&[("n", &self.n), ("e", &self.e)])
+    }
+
+    /// Importe une clé publique JWK (voir `to_jwk`) ; les champs autres que `kty`, `n` et `e` sont ignorés. Comme pour `KeyPair::from_json`,
+    /// l'exposant public doit être impair et le module d'au moins 256.
+    pub fn from_jwk(jwk: &str) -> Result<Self, JsonError>
+    {
+        let fields = parse_flat_object(jwk)?;
+        match fields.iter().find(| (k, _) | k == "kty")
+        {
+            Some((_, kty)) if kty == "RSA" => (),
+            Some((_, kty)) => return Err(JsonError::UnsupportedKeyType(kty.clone())),
+            None => return Err(JsonError::MissingField("kty"))
+        }
+
+        let require = | name: &'static str | -> Result<BigUint, JsonError> {
+            let (_, v) = fields.iter().find(| (k, _) | k == name).ok_or(JsonError::MissingField(name))?;
+            base64::decode_with(v, base64::B64URL_ALPHABET, false).map(| bytes | BigUint::from_bytes_be(&bytes))
+                .map_err(| _ | JsonError::InvalidBase64(String::from(name)))
+        };
+
+        let (n, e) = (require("n")?, require("e")?);
+        if !e.bit(0)
+        {
+            return Err(JsonError::EvenExponent);
+        }
+        if rsa::check_modulus(&n).is_err()
+        {
+            return Err(JsonError::ModulusTooSmall);
+        }
+
+        Ok(PublicKey { n, e })
+    }
+}
+
+impl KeyPair
+{
+    /// Exporte la clé privée au format JWK (voir `PublicKey::to_jwk`) : `d` s'ajoute à `n` et `e`, ainsi que `p`, `q`, `dp`, `dq`
+    /// et `qi` si les paramètres CRT sont présents. Comme pour `to_json`, ceux d'une clé multi-premiers ne sont pas exportés.
+    pub fn to_jwk_private(&self) -> String
+    {
+        let mut fields = vec![("n", &self.n), ("e", &self.e), ("d", &self.d)];
+        if let Some(crt) = self.crt.as_ref().filter(| crt | crt.others.is_empty())
+        {
+            fields.extend_from_slice(&[("p", &crt.p), ("q", &crt.q), ("dp", &crt.dp), ("dq", &crt.dq), ("qi", &crt.qinv)]);
+        }
+
+        jwk_object(&fields)
+    }
+}
+
+
+/// Construit un objet JWK RSA dont les valeurs numériques sont encodées en base64url sans remplissage.
+fn jwk_object(fields: &[(&str, &BigUint)]) -> String
+{
+    let body: Vec<String> = fields.iter().map(| (name, value) | {
+        format!("\"{}\":\"{}\"", name, base64::encode_with(&value.to_bytes_be(), base64::B64URL_ALPHABET, false))
+    }).collect();
+
+    format!("{{\"kty\":\"RSA\",{}}}", body.join(","))
+}
+
+/// Lit un objet JSON plat dont toutes les valeurs sont des chaînes sans échappement, et retourne ses couples (clé, valeur).
+fn parse_flat_object(json: &str) -> Result<Vec<(String, String)>, JsonError>
+{
+    let mut chars = json.trim().chars().peekable();
+    let mut fields = Vec::new();
+
+    // Lit une chaîne entre guillemets, les espaces la précédant étant ignorés
+    let string = | chars: &mut std::iter::Peekable<std::str::Chars> | -> Result<String, JsonError> {
+        if chars.find(| c | !c.is_whitespace()) != Some('"')
+        {
+            return Err(JsonError::Syntax);
+        }
+        let mut s = String::new();
+        loop
+        {
+            match chars.next()
+            {
+                Some('"') => return Ok(s),
+                Some('\\') | None => return Err(JsonError::Syntax),
+                Some(c) => s.push(c)
+            }
+        }
+    };
+    // Retourne le prochain caractère non blanc
+    let next_token = | chars: &mut std::iter::Peekable<std::str::Chars> | -> Option<char> {
+        chars.find(| c | !c.is_whitespace())
+    };
+
+    if next_token(&mut chars) != Some('{')
+    {
+        return Err(JsonError::Syntax);
+    }
+    if chars.clone().find(| c | !c.is_whitespace()) == Some('}')
+    {
+        next_token(&mut chars);
+    }
+    else
+    {
+        loop
+        {
+            let key = string(&mut chars)?;
+            if next_token(&mut chars) != Some(':')
+            {
+                return Err(JsonError::Syntax);
+            }
+            let value = string(&mut chars)?;
+            fields.push((key, value));
+
+            match next_token(&mut chars)
+            {
+                Some(',') => continue,
+                Some('}') => break,
+                _ => return Err(JsonError::Syntax)
+            }
+        }
+    }
+
+    // Rien ne doit suivre l'accolade fermante
+    if next_token(&mut chars).is_some()
+    {
+        return Err(JsonError::Syntax);
+    }
+
+    Ok(fields)
+}
\ No newline at end of file
@@ -38,10 +38,7 @@ impl Display for KeyParseError
 
         if let Some(ul) = &self.underlying
         {
-            if let Err(e) = disp.fmt(f)
-            {
-                return Err(e);
-            }
+            disp.fmt(f)?;
             return ul.fmt(f);
         }
 
@@ -1,8 +1,34 @@
-#[cfg(test)]
-mod tests;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
-mod maths;
+#[cfg(all(test, feature = "std"))]
+mod tests;
 
+pub mod base64;
+pub mod der;
+#[cfg(feature = "std")]
+pub mod elgamal;
+#[cfg(feature = "std")]
 pub mod engines;
+pub mod error;
+pub mod hash;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "std")]
 pub mod keys;
-pub mod messages;
\ No newline at end of file
+#[cfg(feature = "std")]
+pub mod logging;
+pub mod maths;
+#[cfg(feature = "std")]
+pub mod messages;
+#[cfg(feature = "std")]
+pub mod pem;
+pub mod pkcs1;
+#[cfg(feature = "std")]
+pub mod rsa;
+#[cfg(feature = "std")]
+pub mod ssh;
+#[cfg(feature = "std")]
+pub mod stream;
+pub mod zeroize;
\ No newline at end of file
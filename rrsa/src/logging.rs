@@ -0,0 +1,58 @@
+use std::{fmt::Display, sync::RwLock};
+
+
+/// Niveaux de détail des messages de diagnostic émis par la bibliothèque.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level
+{
+    /// Événement très fréquent (chaque candidat premier testé lors de la génération).
+    Trace,
+    /// Étape notable, avec sa durée le cas échéant (nombre premier trouvé, chemin de déchiffrement choisi).
+    Debug,
+    /// Usage déconseillé (clé générée de moins de `rsa::KEY_BITS_RECOMMENDED` bits).
+    Warn
+}
+
+impl Display for Level
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Level::Trace => write!(f, "TRACE"),
+            Level::Debug => write!(f, "DEBUG"),
+            Level::Warn => write!(f, "WARN")
+        }
+    }
+}
+
+
+/// Fonction de réception des messages de diagnostic, installée par `set_hook`.
+pub type Hook = Box<dyn Fn(Level, &str) + Send + Sync>;
+
+/// Fonction de réception installée, partagée par tous les threads.
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+
+/// Installe (`Some`) ou retire (`None`) la fonction recevant les messages de diagnostic de la génération de clés et du déchiffrement,
+/// par exemple pour les transmettre à une bibliothèque de journalisation. Sans fonction installée, aucun message n'est construit.
+/// Sans la fonctionnalité `logging`, aucun message n'est jamais émis.
+pub fn set_hook(hook: Option<Hook>)
+{
+    *HOOK.write().unwrap_or_else(| e | e.into_inner()) = hook;
+}
+
+/// Transmet à la fonction installée le message produit par `msg`, qui n'est construit que si une fonction est installée.
+#[cfg(feature = "logging")]
+pub(crate) fn emit<F: FnOnce() -> String>(level: Level, msg: F)
+{
+    if let Some(hook) = HOOK.read().unwrap_or_else(| e | e.into_inner()).as_ref()
+    {
+        hook(level, &msg());
+    }
+}
+
+/// Sans la fonctionnalité `logging`, les messages ne sont ni construits ni émis.
+#[cfg(not(feature = "logging"))]
+#[inline(always)]
+pub(crate) fn emit<F: FnOnce() -> String>(_level: Level, _msg: F) {}
\ No newline at end of file
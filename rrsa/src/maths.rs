@@ -1,21 +1,89 @@
-use num_bigint::{BigInt, BigUint, RandBigInt};
-use num_traits::{One, identities::Zero};
-use rand::Rng;
-use std::convert::TryInto;
+use num_bigint::{BigInt, BigUint, RandBigInt, ToBigInt};
+use num_traits::{One, Signed, ToPrimitive, identities::Zero};
+use rand::{CryptoRng, Rng};
+use alloc::{vec, vec::Vec};
+use core::{convert::TryInto, error::Error, fmt::Display};
+
+
+/// Erreurs pouvant survenir lors des opérations mathématiques sur les grands entiers et leurs découpages.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MathError
+{
+    /// Le vecteur à recomposer est vide.
+    EmptyVec,
+    /// L'intervalle demandé ne contient aucun nombre premier.
+    NoPrimeInRange,
+    /// Le nombre ne tient pas dans le nombre de blocs demandé, ou dépasse la taille maximale autorisée (`try_rejoin_capped`).
+    TooLarge,
+    /// La base demandée n'est pas comprise entre 2 et 62.
+    InvalidRadix(u32),
+    /// La chaîne est vide ou contient un caractère qui n'est pas un chiffre de la base demandée.
+    InvalidDigit,
+    /// La taille de bloc demandée pour le découpage est nulle ou supérieure à `BLOCK_SZ_MAX` octets (`NumUtil::try_expl`).
+    InvalidBlockSize(u32),
+    /// Le nombre de chiffres ou d'octets du grand entier ne tient pas sur un `u32` (`NumUtil::try_sz`).
+    SizeOverflow,
+    /// Aucun nombre premier n'a été trouvé parmi le nombre maximal de candidats autorisé (`try_rand_prime_bits`, `try_next_prime`...) :
+    /// le générateur aléatoire est probablement défaillant.
+    ExhaustedAttempts
+}
+
+impl Display for MathError
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result
+    {
+        match self
+        {
+            MathError::EmptyVec => write!(f, "Erreur mathématique (MathError) : impossible de recomposer un vecteur vide"),
+            MathError::NoPrimeInRange => write!(f, "Erreur mathématique (MathError) : aucun nombre premier dans l'intervalle demandé"),
+            MathError::TooLarge => write!(f, "Erreur mathématique (MathError) : le nombre ne tient pas dans le nombre de blocs demandé"),
+            MathError::InvalidRadix(radix) => write!(f, "Erreur mathématique (MathError) : base {} non comprise entre 2 et 62", radix),
+            MathError::InvalidDigit => write!(f, "Erreur mathématique (MathError) : chiffre invalide pour la base demandée"),
+            MathError::InvalidBlockSize(sz) => write!(f, "Erreur mathématique (MathError) : taille de bloc {} non comprise entre 1 et {} octets", sz, BLOCK_SZ_MAX),
+            MathError::SizeOverflow => write!(f, "Erreur mathématique (MathError) : taille du nombre non représentable sur 32 bits"),
+            MathError::ExhaustedAttempts => write!(f, "Erreur mathématique (MathError) : nombre maximal de candidats premiers atteint")
+        }
+    }
+}
+
+impl Error for MathError {}
+
+
+/// Taille maximale, en octets, d'un bloc de découpage (`NumUtil::try_expl`) : bien au-delà de la taille des blocs RSA,
+/// elle évite de calculer un diviseur démesuré (et le dépassement de `block_sz * 8` en `u32`).
+pub const BLOCK_SZ_MAX: u32 = 1 << 20;
+/// Taille maximale, en bits, de la fenêtre de `fmodpow_window` : la table précalculée compte `2^(k - 1)` puissances,
+/// et les fenêtres plus larges n'apportent plus rien aux tailles d'exposant usuelles.
+pub const WINDOW_BITS_MAX: u32 = 8;
 
 
 /// Trait d'extension pour les grands entiers non signés. Permet notamment leur découpage et l'obtention de leur taille digitale.
 pub trait NumUtil
 {
+    /// Tente d'obtenir le nombre de chiffres du grand entier dans la base `radix`. Retourne `MathError::InvalidRadix` si la base
+    /// n'est pas comprise entre 2 et 62, et `MathError::SizeOverflow` si le nombre de chiffres ne tient pas sur un `u32`.
+    fn try_sz(&self, radix: u32) -> Result<u32, MathError>;
     /// Permet d'obtenir le nombre de chiffres du grand entier dans la base `radix`.
-    fn sz(&self, radix: u32) -> u32;
+    /// Panique dans les cas d'erreur de `try_sz` : voir `try_sz` pour une version sans panique.
+    fn sz(&self, radix: u32) -> u32
+    {
+        self.try_sz(radix).expect("NumUtil.sz")
+    }
+    /// Tente d'obtenir le nombre d'octets utilisé par le grand entier (voir `try_sz`).
+    fn try_sz_b(&self) -> Result<u32, MathError>
+    {
+        Ok(self.try_sz(16)?.div_ceil(2))
+    }
     /// Permet d'obtenir le nombre d'octets utilisé par le grand entier.
+    /// Panique si ce nombre ne tient pas sur un `u32` : voir `try_sz_b` pour une version sans panique.
     fn sz_b(&self) -> u32
     {
-        (self.sz(16) + 1) / 2
+        self.try_sz_b().expect("NumUtil.sz_b")
     }
 
     /// Remplit un vecteur de grands entiers en découpant le grand entier sur lequel est appliqué la méthode, chaque bloc de taille maximale `block_sz` octets.
+    /// Zéro donne un unique bloc nul, afin que `expl_r(block_sz).rejoin()` soit l'identité y compris sur zéro.
+    /// Panique si `block_sz` est nul ou supérieur à `BLOCK_SZ_MAX` : voir `try_expl` pour une version sans panique.
     fn expl_f(&self, buf: &mut Vec<BigUint>, block_sz: u32);
     /// Découpe l'entier en un vecteur de grands entiers et le retourne, chaque bloc de taille maximale `block_sz` octets.
     fn expl_r(&self, block_sz: u32) -> Vec<BigUint>
@@ -25,18 +93,64 @@ pub trait NumUtil
 
         buf
     }
+    /// Comme `expl_r`, en vérifiant d'abord la taille de bloc : retourne `MathError::InvalidBlockSize` si `block_sz` est nul
+    /// (le découpage ne progresserait jamais) ou supérieur à `BLOCK_SZ_MAX`.
+    fn try_expl(&self, block_sz: u32) -> Result<Vec<BigUint>, MathError>
+    {
+        check_block_sz(block_sz)?;
+
+        Ok(self.expl_r(block_sz))
+    }
+    /// Découpe l'entier en exactement `count` blocs de `block_sz` octets, complétés en tête par des blocs nuls si le nombre est petit.
+    /// Retourne une erreur si le nombre ne tient pas dans `count` blocs. `rejoin_fixed(block_sz)` permet la recomposition exacte.
+    fn expl_n(&self, count: usize, block_sz: u32) -> Result<Vec<BigUint>, MathError>
+    {
+        let parts = self.expl_r(block_sz);
+        if parts.len() > count
+        {
+            return Err(MathError::TooLarge);
+        }
+
+        let mut buf = vec![BigUint::zero(); count - parts.len()];
+        buf.extend(parts);
+
+        Ok(buf)
+    }
 }
 
 impl NumUtil for BigUint
 {
-    fn sz(&self, radix: u32) -> u32
+    /// Les bases 2 et 16 sont déduites directement du nombre de bits, sans conversion des chiffres. Zéro compte pour un chiffre.
+    fn try_sz(&self, radix: u32) -> Result<u32, MathError>
+    {
+        match radix
+        {
+            2 => size_to_u32(self.bits().max(1)),
+            16 => size_to_u32(self.bits().max(1).div_ceil(4)),
+            3..=62 => size_to_u32(self.to_radix_le(radix).len() as u64),
+            _ => Err(MathError::InvalidRadix(radix))
+        }
+    }
+
+    /// Calculé depuis le nombre de bits ; zéro occupe un octet, comme avec `sz(16)`.
+    fn try_sz_b(&self) -> Result<u32, MathError>
     {
-        self.to_str_radix(radix).len().try_into().unwrap()
+        size_to_u32(self.bits().max(1).div_ceil(8))
     }
 
     fn expl_f(&self, buf: &mut Vec<BigUint>, block_sz: u32)
     {
-        let m = BigUint::from(2u8).pow(block_sz * 8);
+        if let Err(e) = check_block_sz(block_sz)
+        {
+            panic!("NumUtil.expl_f : {}", e);
+        }
+        if self.is_zero()
+        {
+            buf.push(BigUint::zero());
+            return;
+        }
+
+        let m = BigUint::one() << (u64::from(block_sz) * 8);
         let mut op = self.clone();
 
         while !op.is_zero()
@@ -50,20 +164,58 @@ impl NumUtil for BigUint
 }
 
 
+/// Convertit un nombre de chiffres ou d'octets en `u32`, ou retourne `MathError::SizeOverflow` s'il ne tient pas sur 32 bits.
+pub(crate) fn size_to_u32(size: u64) -> Result<u32, MathError>
+{
+    size.try_into().map_err(| _ | MathError::SizeOverflow)
+}
+
+/// Vérifie qu'une taille de bloc de découpage est comprise entre 1 et `BLOCK_SZ_MAX` octets.
+fn check_block_sz(block_sz: u32) -> Result<(), MathError>
+{
+    if block_sz == 0 || block_sz > BLOCK_SZ_MAX
+    {
+        return Err(MathError::InvalidBlockSize(block_sz));
+    }
+
+    Ok(())
+}
+
+
 /// Trait d'extension pour les vecteurs de grands entiers. Permet notamment la recomposition de grands nombres.
 pub trait VecNumUtil
 {
+    /// Tente de recomposer un grand nombre depuis ses parties (préalablement découpée avec `expl_f` ou `expl_r`).
+    /// Retourne une erreur si le vecteur est vide.
+    fn try_rejoin(&self) -> Result<BigUint, MathError>;
+    /// Comme `try_rejoin`, mais retourne `MathError::TooLarge` sans rien calculer si le nombre recomposé dépasserait `max_bytes` octets,
+    /// afin de borner la mémoire utilisée lors du décodage de données non fiables.
+    fn try_rejoin_capped(&self, max_bytes: usize) -> Result<BigUint, MathError>;
     /// Recompose un grand nombre depuis ses parties (préalablement découpée avec `expl_f` ou `expl_r`)
-    fn rejoin(&self) -> BigUint;
+    /// Panique si le vecteur est vide : voir `try_rejoin` pour une version sans panique.
+    fn rejoin(&self) -> BigUint
+    {
+        self.try_rejoin().expect("VecNumUtil.rejoin")
+    }
+    /// Tente de recomposer un grand nombre depuis ses parties en considérant que chacune occupe exactement `block_sz` octets.
+    /// Contrairement à `rejoin`, les parties commençant par des octets nuls sont correctement décalées : `expl_r(block_sz).rejoin_fixed(block_sz)` est l'identité.
+    /// Retourne une erreur si le vecteur est vide.
+    fn try_rejoin_fixed(&self, block_sz: u32) -> Result<BigUint, MathError>;
+    /// Recompose un grand nombre depuis ses parties en considérant que chacune occupe exactement `block_sz` octets (voir `try_rejoin_fixed`).
+    /// Panique si le vecteur est vide : voir `try_rejoin_fixed` pour une version sans panique.
+    fn rejoin_fixed(&self, block_sz: u32) -> BigUint
+    {
+        self.try_rejoin_fixed(block_sz).expect("VecNumUtil.rejoin_fixed")
+    }
 }
 
 impl VecNumUtil for Vec<BigUint>
 {
-    fn rejoin(&self) -> BigUint
+    fn try_rejoin(&self) -> Result<BigUint, MathError>
     {
         if self.is_empty()
         {
-            panic!("VecNumUtil.join (BigUint) : vecteur vide");
+            return Err(MathError::EmptyVec);
         }
 
         let mut b = BigUint::from(0u8);
@@ -76,17 +228,46 @@ impl VecNumUtil for Vec<BigUint>
             b = &b * &mult + part;
         }
 
-        b
+        Ok(b)
+    }
+
+    /// La taille du résultat est la somme des tailles en octets des parties.
+    fn try_rejoin_capped(&self, max_bytes: usize) -> Result<BigUint, MathError>
+    {
+        if self.iter().map(| part | part.sz_b() as usize).sum::<usize>() > max_bytes
+        {
+            return Err(MathError::TooLarge);
+        }
+
+        self.try_rejoin()
+    }
+
+    fn try_rejoin_fixed(&self, block_sz: u32) -> Result<BigUint, MathError>
+    {
+        if self.is_empty()
+        {
+            return Err(MathError::EmptyVec);
+        }
+
+        let shift = u64::from(block_sz) * 8;
+        let mut b = BigUint::from(0u8);
+
+        for part in self
+        {
+            b = (b << shift) + part;
+        }
+
+        Ok(b)
     }
 }
 
 impl VecNumUtil for Vec<u8>
 {
-    fn rejoin(&self) -> BigUint
+    fn try_rejoin(&self) -> Result<BigUint, MathError>
     {
         if self.is_empty()
         {
-            panic!("VecNumUtil.join (u8) : vecteur vide");
+            return Err(MathError::EmptyVec);
         }
 
         let mut b = BigUint::from(0u8);
@@ -97,45 +278,324 @@ impl VecNumUtil for Vec<u8>
             b = &b * &mult + part;
         }
 
-        b
+        Ok(b)
+    }
+
+    /// Chaque octet du vecteur occupe un octet du résultat, octets nuls de tête compris.
+    fn try_rejoin_capped(&self, max_bytes: usize) -> Result<BigUint, MathError>
+    {
+        if self.len() > max_bytes
+        {
+            return Err(MathError::TooLarge);
+        }
+
+        self.try_rejoin()
+    }
+
+    fn try_rejoin_fixed(&self, block_sz: u32) -> Result<BigUint, MathError>
+    {
+        if self.is_empty()
+        {
+            return Err(MathError::EmptyVec);
+        }
+
+        let shift = u64::from(block_sz) * 8;
+        let mut b = BigUint::from(0u8);
+
+        for &part in self
+        {
+            b = (b << shift) + part;
+        }
+
+        Ok(b)
     }
 }
 
 
+/// Petits nombres premiers essayés par `expcode` et servant au filtrage rapide de `next_prime`. Interne au module.
 const EXPCODE_TAB: [u8; 35] = [ 2,3,5,7,11,13,17,19,23,29,31,37,41,43,47,53,59,61,67,71,73,79,83,89,97,101,103,107,109,113,127,131,137,139,149 ];
-const PRIME_RN: u32 = 12737213u32;
-/// Nombre d'itérations du test de primalité probabiliste à effectuer.
-const PRIME_ROUNDS: u8 = 20;
+/// Borne par défaut du crible des petits nombres premiers utilisé pour écarter les candidats lors de la génération de clés.
+/// Réglage interne de la génération, hors de l'API publique.
+#[cfg(feature = "std")]
+pub(crate) const SIEVE_LIMIT_DEF: u32 = 2000;
+/// Nombre d'itérations par défaut du test de primalité probabiliste à effectuer. Réglage interne, hors de l'API publique :
+/// les fonctions publiques acceptant un nombre d'itérations documentent cette valeur par défaut (20).
+#[cfg(feature = "std")]
+pub(crate) const PRIME_ROUNDS_DEF: u32 = 20;
+/// Nombre maximal de candidats examinés par défaut lors de la recherche d'un nombre premier, par bit du nombre cherché
+/// (`rand_prime_bits`, `prime_stream`, `rand_safe_prime`, `next_prime`) : bien au-delà de ce qu'exige un générateur sain,
+/// il borne la recherche d'un générateur défaillant. Voir les variantes `try_` pour une limite choisie.
+#[cfg(feature = "std")]
+pub const PRIME_ATTEMPTS_PER_BIT_DEF: u64 = 10000;
+/// Nombre maximal de tirages aléatoires de `rand_prime_in_range` avant de parcourir l'intervalle de manière exhaustive.
+#[cfg(feature = "std")]
+const RANGE_PRIME_DRAWS: u32 = 10000;
+/// Bases de Miller-Rabin (les 13 premiers nombres premiers) rendant le test déterministe sous `MR_DETERMINISTIC_BOUND`.
+const MR_DETERMINISTIC_BASES: [u8; 13] = [ 2,3,5,7,11,13,17,19,23,29,31,37,41 ];
+/// Borne (exclue) sous laquelle aucun nombre composé ne passe le test de Miller-Rabin pour toutes les bases `MR_DETERMINISTIC_BASES`.
+const MR_DETERMINISTIC_BOUND: u128 = 3_317_044_064_679_887_385_961_981;
 
 /// Fonction d'exponentiation rapide, très utile pour le RSA.
+/// Pour un module impair (toujours le cas en RSA), la multiplication de Montgomery est utilisée afin d'éviter une division complète à chaque étape.
+/// Tout autre module (pair, comme pour Rabin ou l'arithmétique modulaire générique, ou égal à 1) passe par la méthode générale
+/// `fmodpow_binary`, qui réduit par division à chaque étape. Panique si `num` est nul.
 pub fn fmodpow(base: &BigUint, exp: &BigUint, num: &BigUint) -> BigUint
 {
-    let mut res = BigUint::from(1u8);
-    let mut exp_bin = exp.clone();
-    let mut temp = base.clone();
-    let mut r;
+    if num.bit(0) && !num.is_one()
+    {
+        fmodpow_montgomery(base, exp, num)
+    }
+    else
+    {
+        fmodpow_binary(base, exp, num)
+    }
+}
+
+/// Exponentiation rapide classique (élévation au carré et multiplication), valable pour tout module non nul, pair ou impair.
+/// C'est la méthode de référence : les chemins optimisés (`fmodpow_montgomery`, `fmodpow_window`) doivent lui donner les mêmes résultats.
+pub(crate) fn fmodpow_binary(base: &BigUint, exp: &BigUint, num: &BigUint) -> BigUint
+{
+    // 1 est réduit lui aussi, afin que tout résultat modulo 1 soit nul, même pour un exposant nul
+    let mut res = BigUint::one() % num;
+    let mut temp = base % num;
+    let bits = exp.bits();
+
+    // Les bits de l'exposant sont lus en place (sans copie ni division de l'exposant), et les produits sont réduits
+    // par valeur afin que `res` et `temp` réutilisent leurs tampons ; seules les multiplications allouent
+    for i in 0..bits
+    {
+        if exp.bit(i)
+        {
+            res = (res * &temp) % num;
+        }
+        // Le carré suivant le bit de poids fort ne servirait pas
+        if i + 1 < bits
+        {
+            temp = (&temp * &temp) % num;
+        }
+    }
+
+    res
+}
+
+/// Exponentiation rapide de gauche à droite, à but pédagogique ou de mesure : retourne le résultat et le nombre de multiplications
+/// modulaires effectuées (élévations au carré comprises). Partant de la base pour le bit de poids fort, chaque bit suivant coûte
+/// un carré, et chaque bit à 1 une multiplication : `bits(exp) - 1` carrés et `popcount(exp) - 1` multiplications au total
+/// (17 pour 65537, soit 16 carrés et 1 multiplication). Un exposant nul ne coûte rien. Panique si `num` est nul.
+pub fn fmodpow_counted(base: &BigUint, exp: &BigUint, num: &BigUint) -> (BigUint, u64)
+{
+    if exp.is_zero()
+    {
+        return (BigUint::one() % num, 0);
+    }
+
+    let base = base % num;
+    let mut res = base.clone();
+    let mut count = 0u64;
+
+    for i in (0..(exp.bits() - 1)).rev()
+    {
+        res = (&res * &res) % num;
+        count += 1;
+        if exp.bit(i)
+        {
+            res = (res * &base) % num;
+            count += 1;
+        }
+    }
+
+    (res, count)
+}
+
+/// Exponentiation modulaire sur des entiers signés, le résultat étant le représentant canonique dans `[0, num)`.
+/// Une base négative est d'abord ramenée dans `[0, num)` ; un exposant négatif élève l'inverse modulaire de la base à l'opposé de l'exposant.
+/// Retourne `None` si `num` n'est pas strictement positif, ou si l'exposant est négatif et que la base n'est pas inversible modulo `num`.
+pub fn fmodpow_signed(base: &BigInt, exp: &BigInt, num: &BigInt) -> Option<BigInt>
+{
+    if !num.is_positive()
+    {
+        return None;
+    }
+
+    let mut base = ((base % num) + num) % num;
+    if exp.is_negative()
+    {
+        base = mod_inverse(&base, num)?;
+    }
+
+    // Les trois valeurs sont positives ou nulles : les conversions ne peuvent pas échouer
+    Some(fmodpow(&base.to_biguint().unwrap(), exp.magnitude(), &num.to_biguint().unwrap()).into())
+}
+
+/// Exponentiation rapide dans l'espace de Montgomery. Le module `num` doit être impair et supérieur à 1.
+pub(crate) fn fmodpow_montgomery(base: &BigUint, exp: &BigUint, num: &BigUint) -> BigUint
+{
+    Montgomery::new(num).pow(base, &exp_bits(exp))
+}
+
+/// Exponentiation modulaire d'une série de bases par un même exposant et un même module (vérification de nombreuses signatures
+/// d'une même clé publique par exemple). La décomposition binaire de l'exposant et, pour un module impair, les constantes de Montgomery
+/// ne sont calculées qu'une seule fois. Le résultat est identique à l'appel de `fmodpow` sur chaque base.
+pub fn fmodpow_batch(bases: &[BigUint], exp: &BigUint, num: &BigUint) -> Vec<BigUint>
+{
+    if num.bit(0) && !num.is_one()
+    {
+        let bits = exp_bits(exp);
+        let ctx = Montgomery::new(num);
+        bases.iter().map(| base | ctx.pow(base, &bits)).collect()
+    }
+    else
+    {
+        bases.iter().map(| base | fmodpow_binary(base, exp, num)).collect()
+    }
+}
+
+/// Décomposition binaire d'un exposant, du bit de poids faible au bit de poids fort.
+fn exp_bits(exp: &BigUint) -> Vec<bool>
+{
+    (0..exp.bits()).map(| i | exp.bit(i)).collect()
+}
+
+/// Constantes de la multiplication de Montgomery pour un module impair `num` supérieur à 1.
+struct Montgomery<'a>
+{
+    num: &'a BigUint,
+    /// R = 2^k avec R > num ; les réductions modulo R se font alors par masque et les divisions par décalage
+    k: u64,
+    mask: BigUint,
+    /// n' = -num^-1 mod R, qui existe puisque num est impair
+    nprime: BigUint
+}
+
+impl<'a> Montgomery<'a>
+{
+    fn new(num: &'a BigUint) -> Self
+    {
+        let k = num.bits();
+        let mask = (BigUint::one() << k) - 1u8;
+        let ninv = mod_inverse(&num.to_bigint().unwrap(), &(BigInt::one() << k)).unwrap().to_biguint().unwrap();
+        let nprime = (&mask + 1u8) - ninv;
+
+        Montgomery { num, k, mask, nprime }
+    }
+
+    /// Réduction de Montgomery : retourne t * R^-1 mod num pour t < num * R.
+    /// `t` est pris par valeur : l'addition, le décalage et la soustraction finale se font dans son tampon.
+    fn redc(&self, t: BigUint) -> BigUint
+    {
+        let m = ((&t & &self.mask) * &self.nprime) & &self.mask;
+        let mut u = (t + m * self.num) >> self.k;
+        if &u >= self.num
+        {
+            u -= self.num;
+        }
+        u
+    }
+
+    /// Élève `base` à la puissance dont la décomposition binaire (poids faible en premier) est `bits`.
+    fn pow(&self, base: &BigUint, bits: &[bool]) -> BigUint
+    {
+        // Passage dans l'espace de Montgomery : x -> x * R mod num
+        let mut temp = (base << self.k) % self.num;
+        let mut res = (BigUint::one() << self.k) % self.num;
+
+        for (i, &bit) in bits.iter().enumerate()
+        {
+            if bit
+            {
+                res = self.redc(res * &temp);
+            }
+            // Le carré suivant le bit de poids fort ne servirait pas
+            if i + 1 < bits.len()
+            {
+                temp = self.redc(&temp * &temp);
+            }
+        }
+
+        // Retour dans l'espace usuel
+        self.redc(res)
+    }
+}
+
+/// Exponentiation modulaire par fenêtre glissante de `k` bits : les puissances impaires `base^1, base^3, ..., base^(2^k - 1)` sont précalculées,
+/// puis l'exposant est parcouru par fenêtres, ce qui réduit le nombre de multiplications pour les grands exposants (exposant privé RSA par exemple).
+/// La taille de fenêtre est ramenée entre 1 et `WINDOW_BITS_MAX` bits : une fenêtre nulle est traitée comme une fenêtre de 1 bit,
+/// une fenêtre plus large que `WINDOW_BITS_MAX` comme une fenêtre de `WINDOW_BITS_MAX` bits.
+pub fn fmodpow_window(base: &BigUint, exp: &BigUint, num: &BigUint, k: u32) -> BigUint
+{
+    let k = u64::from(k.clamp(1, WINDOW_BITS_MAX));
+    let base = base % num;
+
+    // Table des puissances impaires : table[i] = base^(2i + 1) mod num
+    let b2 = (&base * &base) % num;
+    let mut table = vec![base];
+    for i in 1..(1usize << (k - 1))
+    {
+        let next = (&table[i - 1] * &b2) % num;
+        table.push(next);
+    }
 
-    while !exp_bin.is_zero()
+    let mut res = BigUint::one() % num;
+    let mut i = exp.bits();
+
+    // `i` est le nombre de bits restant à traiter (le prochain bit est donc le bit i - 1)
+    while i > 0
     {
-        r = &exp_bin % 2u8;
-        if r.is_one()
+        if !exp.bit(i - 1)
+        {
+            res = (&res * &res) % num;
+            i -= 1;
+            continue;
+        }
+
+        // On cherche la plus longue fenêtre (au plus k bits) commençant au bit i - 1 et se terminant par un bit à 1
+        let mut j = i.saturating_sub(k);
+        while !exp.bit(j)
+        {
+            j += 1;
+        }
+
+        let mut val = 0usize;
+        for b in (j..i).rev()
         {
-            res = (&res * &temp) % num;
+            val = (val << 1) | usize::from(exp.bit(b));
+            res = (&res * &res) % num;
         }
+        res = (&res * &table[val / 2]) % num;
 
-        exp_bin /= 2u8;
-        temp = (&temp * &temp) % num;
+        i = j;
     }
 
     res
 }
 
-/// Algorithme d'Euclide pour trouver le PGCD de deux nombres. Utile pour le RSA.
-pub fn euclide(a: &BigInt, b: &BigInt) -> BigInt
+/// Addition modulaire : retourne `(a + b) mod n`, dans `[0, n[`. Les opérandes n'ont pas besoin d'être déjà réduits.
+/// Panique si `n` est nul (comme l'ensemble des opérations modulaires).
+pub fn mod_add(a: &BigUint, b: &BigUint, n: &BigUint) -> BigUint
+{
+    (a + b) % n
+}
+
+/// Soustraction modulaire : retourne `(a - b) mod n`, dans `[0, n[`, y compris lorsque `a < b` (n est alors ajouté à la différence).
+/// Panique si `n` est nul.
+pub fn mod_sub(a: &BigUint, b: &BigUint, n: &BigUint) -> BigUint
+{
+    // a mod n + n étant strictement supérieur à b mod n, la différence ne peut être négative
+    (a % n + n - b % n) % n
+}
+
+/// Multiplication modulaire : retourne `(a * b) mod n`, dans `[0, n[`. Panique si `n` est nul.
+pub fn mod_mul(a: &BigUint, b: &BigUint, n: &BigUint) -> BigUint
+{
+    (a * b) % n
+}
+
+/// Algorithme d'Euclide étendu : retourne le triplet `(pgcd, u, v)` tel que `a * u + b * v == pgcd`.
+pub fn bezout(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt)
 {
     let (mut r1, mut r2) = (a.clone(), b.clone());
     let (mut u1, mut u2) = (BigInt::from(1u8), BigInt::from(0u8));
-    let (mut v1, mut v2) = (BigInt::from(1u8), BigInt::from(0u8));
+    let (mut v1, mut v2) = (BigInt::from(0u8), BigInt::from(1u8));
     let (mut u3, mut v3, mut r3);
     let mut q;
 
@@ -153,54 +613,963 @@ pub fn euclide(a: &BigInt, b: &BigInt) -> BigInt
         v2 = &v3 - &q * &v1;
     }
 
-    u1
+    (r1, u1, v1)
 }
 
-/// Retourne le code d'exposant d'un nombre.
-pub fn expcode(num: &BigUint) -> Option<BigUint>
+/// Algorithme d'Euclide pour trouver le PGCD de deux nombres. Utile pour le RSA.
+/// Retourne uniquement le coefficient de Bézout de `a` (voir `bezout` pour le résultat complet).
+pub fn euclide(a: &BigInt, b: &BigInt) -> BigInt
 {
-    for &i in EXPCODE_TAB.iter()
+    bezout(a, b).1
+}
+
+/// Retourne le PGCD de deux grands entiers positifs par l'algorithme d'Euclide simple (sans calcul des coefficients de Bézout).
+pub fn gcd(a: &BigUint, b: &BigUint) -> BigUint
+{
+    let (mut r1, mut r2) = (a.clone(), b.clone());
+
+    while !r2.is_zero()
     {
-        if !(num % i).is_zero()
+        let r3 = &r1 % &r2;
+        r1 = r2;
+        r2 = r3;
+    }
+
+    r1
+}
+
+/// Retourne le PPCM de deux grands entiers positifs, ou zéro si l'un des deux est nul.
+/// Utile notamment pour l'indicatrice de Carmichael `lambda(n) = ppcm(p - 1, q - 1)`.
+pub fn lcm(a: &BigUint, b: &BigUint) -> BigUint
+{
+    if a.is_zero() || b.is_zero()
+    {
+        return BigUint::zero();
+    }
+
+    // On divise avant de multiplier pour limiter la taille des intermédiaires
+    a / gcd(a, b) * b
+}
+
+/// Indicatrice d'Euler d'un nombre dont la factorisation est donnée sous la forme de couples (nombre premier p, exposant k) distincts :
+/// `phi(p1^k1 * ... * pr^kr) = p1^(k1 - 1) (p1 - 1) * ... * pr^(kr - 1) (pr - 1)`. Les couples d'exposant nul sont ignorés.
+pub fn phi_from_primes(primes: &[(BigUint, u32)]) -> BigUint
+{
+    primes.iter().filter(| (_, k) | *k > 0).fold(BigUint::one(), | acc, (p, k) | acc * p.pow(k - 1) * (p - 1u8))
+}
+
+/// Indicatrice de Carmichael (plus petit exposant `lambda` tel que `a^lambda ≡ 1` pour tout `a` inversible) d'un nombre dont la
+/// factorisation est donnée comme pour `phi_from_primes` : c'est le PPCM des `lambda(p^k)`, égaux à `phi(p^k)` sauf pour les
+/// puissances de 2 à partir de 8, où `lambda(2^k) = 2^(k - 2)`.
+pub fn lambda_from_primes(primes: &[(BigUint, u32)]) -> BigUint
+{
+    primes.iter().filter(| (_, k) | *k > 0).fold(BigUint::one(), | acc, (p, k) |
+    {
+        let lambda = if *p == BigUint::from(2u8) && *k >= 3
         {
-            return Some(BigUint::from(i));
+            BigUint::one() << (k - 2)
         }
+        else
+        {
+            p.pow(k - 1) * (p - 1u8)
+        };
+        lcm(&acc, &lambda)
+    })
+}
+
+/// Inverse modulaire sans algorithme d'Euclide, lorsque l'indicatrice d'Euler `phi` de `n` est connue : d'après le théorème d'Euler,
+/// `a^(phi - 1) mod n` est l'inverse de `a` dès que `a` et `n` sont premiers entre eux. Le calcul est une seule exponentiation modulaire,
+/// sans branchement dépendant de `a` autre que ceux de `fmodpow`, ce qui le destine aux valeurs secrètes (désaveuglement du déchiffrement).
+/// Si `a` n'est pas inversible, le résultat n'est pas un inverse : c'est à l'appelant de le vérifier (`a * x mod n == 1`). Panique si `phi` est nul.
+pub fn mod_inverse_prime_power(a: &BigUint, n: &BigUint, phi: &BigUint) -> BigUint
+{
+    fmodpow(a, &(phi - 1u8), n)
+}
+
+/// Retourne l'inverse modulaire de `a` modulo `modulus`, soit l'entier `x` de [0, modulus) tel que `a * x ≡ 1 (mod modulus)`.
+/// Retourne `None` si l'inverse n'existe pas, c'est-à-dire si `a` et `modulus` ne sont pas premiers entre eux.
+pub fn mod_inverse(a: &BigInt, modulus: &BigInt) -> Option<BigInt>
+{
+    if modulus <= &BigInt::zero()
+    {
+        return None;
+    }
+    if modulus.is_one()
+    {
+        return Some(BigInt::zero());
     }
 
-    None
+    // On ramène `a` dans [0, modulus) pour que le coefficient de Bézout soit celui de l'inverse
+    let a = ((a % modulus) + modulus) % modulus;
+    let (g, mut u, _) = bezout(&a, modulus);
+
+    // Pas d'inverse si `a` et `modulus` ne sont pas premiers entre eux
+    if !g.is_one()
+    {
+        return None;
+    }
+
+    // Le coefficient de Bézout peut être négatif
+    while u.is_negative()
+    {
+        u += modulus;
+    }
+    u %= modulus;
+
+    Some(u)
 }
 
-/// Retourne vrai si le grand entier `num` est premier, faux sinon.
-/// Le test est probabiliste et peut se tromper ; avec un nombre assez grand d'itérations `PRIME_ROUNDS`, cela est toutefois peu probable.
-pub fn isprime(num: &BigUint) -> bool
+/// Théorème des restes chinois : retourne l'unique `x` modulo le produit des `moduli` tel que `x ≡ residues[i] (mod moduli[i])` pour tout `i`.
+/// Les congruences sont combinées deux à deux. Retourne `None` si les modules ne sont pas premiers entre eux deux à deux,
+/// si l'un d'eux est nul, ou si les deux tranches sont vides ou de longueurs différentes.
+pub fn crt(residues: &[BigUint], moduli: &[BigUint]) -> Option<BigUint>
 {
-    // Le test étant probabiliste, il faut faire plusieurs itérations pour être raisonnablement certain du résultat
-    for _ in 0..PRIME_ROUNDS
+    if residues.is_empty() || residues.len() != moduli.len()
+    {
+        return None;
+    }
+
+    let (mut x, mut m) = (BigInt::zero(), BigInt::one());
+    for (r, mi) in residues.iter().zip(moduli.iter())
     {
-        if !fmodpow(&(&PRIME_RN % num), &(num - 1u8), num).is_one()
+        let (r, mi) = (r.to_bigint().unwrap(), mi.to_bigint().unwrap());
+        // x + m * k ≡ r (mod mi)  <=>  k ≡ (r - x) * m^-1 (mod mi)
+        let inv = mod_inverse(&m, &mi)?;
+        let k = ((((&r - &x) % &mi) + &mi) * inv) % &mi;
+        x += &m * k;
+        m *= mi;
+    }
+
+    x.to_biguint()
+}
+
+/// Symbole de Jacobi `(a / n)` pour `n` impair et positif : retourne -1, 0 ou 1, et 1 pour `n == 1`.
+/// Le calcul se fait par réductions successives grâce à la loi de réciprocité quadratique, sans factoriser `n`.
+/// Panique si `n` est pair (nul compris), le symbole n'y étant pas défini.
+pub fn jacobi(a: &BigInt, n: &BigUint) -> i8
+{
+    assert!(n.bit(0), "maths.jacobi : n doit être impair");
+
+    let n_int = n.to_bigint().unwrap();
+    let mut a = (((a % &n_int) + &n_int) % &n_int).to_biguint().unwrap();
+    let mut n = n.clone();
+    let mut result = 1i8;
+
+    while !a.is_zero()
+    {
+        // (2 / n) = -1 si et seulement si n ≡ 3 ou 5 (mod 8)
+        let twos = a.trailing_zeros().unwrap();
+        a >>= twos;
+        let n_mod8 = (&n % 8u8).to_u8().unwrap();
+        if twos % 2 == 1 && (n_mod8 == 3 || n_mod8 == 5)
         {
-            return false;
+            result = -result;
+        }
+
+        // Réciprocité : (a / n) = -(n / a) si a ≡ n ≡ 3 (mod 4)
+        core::mem::swap(&mut a, &mut n);
+        if (&a % 4u8).to_u8() == Some(3) && (&n % 4u8).to_u8() == Some(3)
+        {
+            result = -result;
         }
+        a %= &n;
     }
 
-    true
+    if n.is_one() { result } else { 0 }
 }
 
-/// Retourne un grand entier constitué de `szb` octets avec une bonne probabilité qu'il soit premier.
-pub fn rand_primelike(szb: u64) -> BigUint
+/// Racine carrée entière de `n` (partie entière de sa racine carrée), par la méthode de Newton : partant d'une valeur supérieure
+/// à la racine, l'itération `x <- (x + n / x) / 2` décroît strictement jusqu'à atteindre la partie entière.
+pub fn isqrt(n: &BigUint) -> BigUint
 {
-    let mut b = rand::thread_rng().gen_biguint(szb * 8);
-    // On met le dernier chiffre à zéro
-    b /= 10u8;
-    b *= 10u8;
-
-    // On génère un chiffre impair qui n'est pas 5 afin d'augmenter les chances que le nombre soit premier
-    let mut digit = 0u8;
-    while digit % 2 == 0 || digit == 5
+    if n.is_zero()
     {
-        digit = rand::thread_rng().gen_range(1..10);
+        return BigUint::zero();
     }
-    b += digit;
 
-    b
+    // 2^ceil(bits / 2) est supérieur ou égal à la racine
+    let mut x = BigUint::one() << n.bits().div_ceil(2);
+    loop
+    {
+        let y = (&x + n / &x) >> 1u8;
+        if y >= x
+        {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Retourne vrai si `n` est un carré parfait (voir `isqrt`).
+pub fn is_perfect_square(n: &BigUint) -> bool
+{
+    let root = isqrt(n);
+    &root * &root == *n
+}
+
+/// Racine carrée de `a` modulo le nombre premier `p` (algorithme de Tonelli-Shanks) : retourne `x` tel que `x² ≡ a (mod p)`,
+/// ou `None` si `a` n'est pas un résidu quadratique (symbole de Legendre égal à -1). `p` n'est pas vérifié et doit être premier.
+/// Si `p ≡ 3 (mod 4)`, la racine est directement `a^((p + 1) / 4)`.
+pub fn sqrt_mod(a: &BigUint, p: &BigUint) -> Option<BigUint>
+{
+    let a = a % p;
+    if *p == BigUint::from(2u8) || a.is_zero()
+    {
+        return Some(a);
+    }
+    if jacobi(&a.to_bigint().unwrap(), p) != 1
+    {
+        return None;
+    }
+    if (p % 4u8).to_u8() == Some(3)
+    {
+        return Some(fmodpow(&a, &((p + 1u8) >> 2u8), p));
+    }
+
+    // p - 1 = q * 2^s avec q impair
+    let pm1 = p - 1u8;
+    let s = pm1.trailing_zeros().unwrap();
+    let q = &pm1 >> s;
+
+    // Un non-résidu quadratique z quelconque (la moitié des éléments l'est)
+    let mut z = BigUint::from(2u8);
+    while jacobi(&z.to_bigint().unwrap(), p) != -1
+    {
+        z += 1u8;
+    }
+
+    let mut m = s;
+    let mut c = fmodpow(&z, &q, p);
+    let mut t = fmodpow(&a, &q, p);
+    let mut r = fmodpow(&a, &((&q + 1u8) >> 1u8), p);
+
+    while !t.is_one()
+    {
+        // Plus petit i tel que t^(2^i) = 1
+        let mut i = 0;
+        let mut t2 = t.clone();
+        while !t2.is_one()
+        {
+            t2 = (&t2 * &t2) % p;
+            i += 1;
+        }
+
+        let b = fmodpow(&c, &(BigUint::one() << (m - i - 1)), p);
+        m = i;
+        c = (&b * &b) % p;
+        t = (t * &c) % p;
+        r = (r * b) % p;
+    }
+
+    Some(r)
+}
+
+/// Retourne le code d'exposant d'un nombre : le plus petit nombre premier d'`EXPCODE_TAB` qui ne divise pas `num`, ou `None` si tous le divisent.
+/// Fonction historique du moteur `engines::Rsa`, limitée à une table fixe ; voir `find_public_exponent` pour choisir un exposant public.
+pub fn expcode(num: &BigUint) -> Option<BigUint>
+{
+    for &i in EXPCODE_TAB.iter()
+    {
+        if !(num % i).is_zero()
+        {
+            return Some(BigUint::from(i));
+        }
+    }
+
+    None
+}
+
+/// Retourne un exposant public RSA utilisable pour l'indicatrice `phi`, c'est-à-dire premier avec elle : 65537 de préférence,
+/// sinon le plus petit entier impair supérieur à 1 premier avec `phi`. La recherche aboutit toujours, tout nombre premier
+/// ne divisant pas `phi` convenant. Panique si `phi` est nul.
+pub fn find_public_exponent(phi: &BigUint) -> BigUint
+{
+    assert!(!phi.is_zero(), "find_public_exponent : l'indicatrice ne peut pas être nulle");
+
+    let preferred = BigUint::from(65537u32);
+    if gcd(&preferred, phi).is_one()
+    {
+        return preferred;
+    }
+
+    let mut e = BigUint::from(3u8);
+    while !gcd(&e, phi).is_one()
+    {
+        e += 2u8;
+    }
+
+    e
+}
+
+/// Retourne vrai si le grand entier `num` est premier, faux sinon, avec 20 itérations (`PRIME_ROUNDS_DEF`) (voir `is_prime_rounds`).
+#[cfg(feature = "std")]
+pub fn isprime(num: &BigUint) -> bool
+{
+    is_prime_rounds(num, PRIME_ROUNDS_DEF)
+}
+
+/// Retourne vrai si le grand entier `num` est premier, faux sinon.
+/// Le test utilisé est celui de Miller-Rabin avec `rounds` itérations : il est probabiliste et peut se tromper ; un nombre composé
+/// est déclaré premier avec une probabilité d'au plus 4^-rounds. Moins d'itérations accélèrent la génération de clés jetables.
+#[cfg(feature = "std")]
+pub fn is_prime_rounds(num: &BigUint, rounds: u32) -> bool
+{
+    // Les petits cas sont traités directement : 0 et 1 ne sont pas premiers, 2 et 3 le sont
+    if num < &BigUint::from(4u8)
+    {
+        return num > &BigUint::one();
+    }
+    // Un nombre pair (autre que 2) n'est jamais premier
+    if !num.bit(0)
+    {
+        return false;
+    }
+
+    // On décompose num - 1 en d * 2^s avec d impair
+    let nm1 = num - 1u8;
+    let s = nm1.trailing_zeros().unwrap();
+    let d = &nm1 >> s;
+    let (low, high) = (BigUint::from(2u8), &nm1);
+    let mut rng = rand::thread_rng();
+
+    // Le test étant probabiliste, il faut faire plusieurs itérations pour être raisonnablement certain du résultat
+    // Chaque itération utilise une base aléatoire indépendante dans [2, num - 2]
+    (0..rounds).all(| _ | !is_mr_witness(&rng.gen_biguint_range(&low, high), &d, s, num))
+}
+
+/// Test de Miller-Rabin déterministe : retourne `Some(vrai)` si `num` est premier et `Some(faux)` sinon lorsque `num` est inférieur
+/// à 3 317 044 064 679 887 385 961 981, les 13 premiers nombres premiers suffisant alors comme bases. Retourne `None` au-delà :
+/// l'appelant doit se rabattre sur le test probabiliste (`is_prime_rounds`).
+pub fn is_prime_deterministic(num: &BigUint) -> Option<bool>
+{
+    if num >= &BigUint::from(MR_DETERMINISTIC_BOUND)
+    {
+        return None;
+    }
+    if num < &BigUint::from(4u8)
+    {
+        return Some(num > &BigUint::one());
+    }
+    if !num.bit(0)
+    {
+        return Some(false);
+    }
+
+    let nm1 = num - 1u8;
+    let s = nm1.trailing_zeros().unwrap();
+    let d = &nm1 >> s;
+
+    Some(MR_DETERMINISTIC_BASES.iter().all(| &a |
+    {
+        // Une base multiple de num (num étant alors cette base, donc premier) ne témoigne de rien
+        let a = BigUint::from(a) % num;
+        a.is_zero() || !is_mr_witness(&a, &d, s, num)
+    }))
+}
+
+/// Retourne vrai si `a` est un témoin de Miller-Rabin de la non-primalité de l'entier impair `num`, où `num - 1 = d * 2^s` avec d impair.
+fn is_mr_witness(a: &BigUint, d: &BigUint, s: u64, num: &BigUint) -> bool
+{
+    let nm1 = num - 1u8;
+    let mut x = fmodpow(a, d, num);
+
+    if x.is_one() || x == nm1
+    {
+        return false;
+    }
+
+    for _ in 1..s
+    {
+        x = (&x * &x) % num;
+        if x == nm1
+        {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Retourne vrai si le grand entier `num` est (probablement) premier selon le test de Solovay-Strassen avec `rounds` itérations.
+/// Chaque itération vérifie le critère d'Euler `a^((num - 1) / 2) ≡ (a / num) (mod num)` pour une base aléatoire `a` tirée par `rng` ;
+/// un nombre composé passe une itération avec une probabilité d'au plus 1/2.
+pub fn is_prime_solovay(num: &BigUint, rounds: u32, rng: &mut impl Rng) -> bool
+{
+    if num < &BigUint::from(4u8)
+    {
+        return num > &BigUint::one();
+    }
+    if !num.bit(0)
+    {
+        return false;
+    }
+
+    let nm1 = num - 1u8;
+    let half = &nm1 >> 1u8;
+    let (low, high) = (BigUint::from(2u8), &nm1);
+
+    for _ in 0..rounds
+    {
+        let a = rng.gen_biguint_range(&low, high);
+        let expected = match jacobi(&a.to_bigint().unwrap(), num)
+        {
+            // a partage un facteur avec num : num est composé
+            0 => return false,
+            1 => BigUint::one(),
+            _ => nm1.clone()
+        };
+
+        if fmodpow(&a, &half, num) != expected
+        {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Retourne vrai si le grand entier `num` est (probablement) premier selon le test de Baillie-PSW : un test de Miller-Rabin fort
+/// en base 2, suivi d'un test de Lucas fort (paramètres de Selfridge, voir `is_strong_lucas_prp`).
+/// Le test est déterministe et aucun nombre composé ne le passe à ce jour, ce qui en fait un bon complément à `is_prime_rounds`.
+pub fn is_prime_bpsw(num: &BigUint) -> bool
+{
+    if num < &BigUint::from(4u8)
+    {
+        return num > &BigUint::one();
+    }
+    if !num.bit(0)
+    {
+        return false;
+    }
+
+    let nm1 = num - 1u8;
+    let s = nm1.trailing_zeros().unwrap();
+    if is_mr_witness(&BigUint::from(2u8), &(&nm1 >> s), s, num)
+    {
+        return false;
+    }
+
+    // Aucun D convenable n'existe pour un carré parfait : la recherche ne se terminerait pas
+    !is_perfect_square(num) && is_strong_lucas_prp(num)
+}
+
+/// Test de Lucas fort pour `num` impair, supérieur à 3 et non carré parfait. D est le premier terme de la suite 5, -7, 9, -11, ...
+/// tel que `(D / num) == -1` (méthode A de Selfridge), avec `P = 1` et `Q = (1 - D) / 4`. En écrivant `num + 1 = d * 2^s` avec `d` impair,
+/// `num` est un nombre premier probable de Lucas fort si `U_d ≡ 0` ou si `V_(d * 2^r) ≡ 0 (mod num)` pour un `0 <= r < s`.
+fn is_strong_lucas_prp(num: &BigUint) -> bool
+{
+    let num_int = num.to_bigint().unwrap();
+    let mut d_sel = BigInt::from(5u8);
+
+    loop
+    {
+        match jacobi(&d_sel, num)
+        {
+            -1 => break,
+            // D partage un facteur avec num : num est composé, sauf s'il s'agit de ce facteur lui-même
+            0 if d_sel.abs() != num_int => return false,
+            _ => {}
+        }
+        d_sel = if d_sel.is_positive() { -(d_sel + 2u8) } else { -d_sel + 2u8 };
+    }
+
+    let (p, q) = (BigInt::one(), (BigInt::one() - &d_sel) / 4u8);
+    let np1 = num + 1u8;
+    let s = np1.trailing_zeros().unwrap();
+    let (u, mut v, mut qk) = lucas_sequence(&p, &q, &(&np1 >> s), num);
+
+    if u.is_zero()
+    {
+        return true;
+    }
+    for _ in 0..s
+    {
+        if v.is_zero()
+        {
+            return true;
+        }
+        // V_2k = V_k^2 - 2 Q^k et Q^2k = (Q^k)^2
+        v = mod_sub(&mod_mul(&v, &v, num), &(&qk << 1u8), num);
+        qk = mod_mul(&qk, &qk, num);
+    }
+
+    false
+}
+
+/// Termes `(U_k mod n, V_k mod n)` des suites de Lucas de paramètres `P` et `Q` (éventuellement négatifs), définies par
+/// `U_0 = 0`, `U_1 = 1`, `V_0 = 2`, `V_1 = P` et `X_(k+2) = P X_(k+1) - Q X_k`, calculés en `O(log k)` par les formules de doublement.
+/// Avec `P = 1` et `Q = -1`, on obtient par exemple les nombres de Fibonacci et de Lucas. Panique si `n` est pair.
+pub fn lucas_uv(p: &BigInt, q: &BigInt, k: &BigUint, n: &BigUint) -> (BigUint, BigUint)
+{
+    assert!(n.bit(0), "maths.lucas_uv : n doit être impair");
+
+    let (u, v, _) = lucas_sequence(p, q, k, n);
+    (u, v)
+}
+
+/// Calcule `(U_k mod n, V_k mod n, Q^k mod n)` pour les suites de Lucas de paramètres `P` et `Q`, par les formules de doublement
+/// en parcourant les bits de `k` depuis le poids fort. `n` doit être impair (les divisions par 2 se font modulo `n`).
+fn lucas_sequence(p: &BigInt, q: &BigInt, k: &BigUint, n: &BigUint) -> (BigUint, BigUint, BigUint)
+{
+    let n_int = n.to_bigint().unwrap();
+    let reduce = | x: &BigInt | (((x % &n_int) + &n_int) % &n_int).to_biguint().unwrap();
+    let (p, q) = (reduce(p), reduce(q));
+    // D = P^2 - 4Q
+    let d = mod_sub(&mod_mul(&p, &p, n), &mod_mul(&BigUint::from(4u8), &q, n), n);
+    // Division par 2 modulo n impair : un nombre impair est d'abord rendu pair par l'ajout de n
+    let half = | x: BigUint | if x.bit(0) { (x + n) >> 1u8 } else { x >> 1u8 };
+
+    let (mut u, mut v, mut qk) = (BigUint::zero(), BigUint::from(2u8) % n, BigUint::one() % n);
+    for i in (0..k.bits()).rev()
+    {
+        // U_2k = U_k V_k, V_2k = V_k^2 - 2 Q^k
+        u = mod_mul(&u, &v, n);
+        v = mod_sub(&mod_mul(&v, &v, n), &(&qk << 1u8), n);
+        qk = mod_mul(&qk, &qk, n);
+
+        if k.bit(i)
+        {
+            // U_(k+1) = (P U_k + V_k) / 2, V_(k+1) = (D U_k + P V_k) / 2
+            let (pu, du) = (mod_mul(&p, &u, n), mod_mul(&d, &u, n));
+            u = half(mod_add(&pu, &v, n));
+            v = half(mod_add(&du, &mod_mul(&p, &v, n), n));
+            qk = mod_mul(&qk, &q, n);
+        }
+    }
+
+    (u, v, qk)
+}
+
+/// Retourne le plus petit nombre premier (probable) strictement supérieur à `start`.
+/// Les candidats impairs sont parcourus deux par deux ; ceux divisibles par un petit nombre premier (crible jusqu'à `SIEVE_LIMIT_DEF`)
+/// sont écartés avant le test de Miller-Rabin. Panique si aucun des `PRIME_ATTEMPTS_PER_BIT_DEF` candidats par bit de `start`
+/// n'est premier, ce qui n'arrive pas en pratique : voir `try_next_prime` pour une version sans panique.
+#[cfg(feature = "std")]
+pub fn next_prime(start: &BigUint) -> BigUint
+{
+    try_next_prime(start, default_max_attempts(start.bits())).expect("maths.next_prime : nombre maximal de candidats atteint")
+}
+
+/// Voir `next_prime`, en examinant au plus `max_attempts` candidats impairs : au-delà, retourne `MathError::ExhaustedAttempts`.
+#[cfg(feature = "std")]
+pub fn try_next_prime(start: &BigUint, max_attempts: u64) -> Result<BigUint, MathError>
+{
+    if *start < BigUint::from(2u8)
+    {
+        return Ok(BigUint::from(2u8));
+    }
+
+    let mut candidate = start + 1u8;
+    if !candidate.bit(0)
+    {
+        candidate += 1u8;
+    }
+
+    let sieve = small_prime_sieve(SIEVE_LIMIT_DEF);
+    for _ in 0..max_attempts
+    {
+        if !divisible_by_small(&candidate, &sieve) && isprime(&candidate)
+        {
+            return Ok(candidate);
+        }
+        candidate += 2u8;
+    }
+
+    Err(MathError::ExhaustedAttempts)
+}
+
+/// Retourne tous les nombres premiers strictement inférieurs à `limit`, calculés par le crible d'Ératosthène.
+pub fn small_prime_sieve(limit: u32) -> Vec<u32>
+{
+    let limit = limit as usize;
+    let mut composite = vec![false; limit];
+    let mut primes = Vec::new();
+
+    for i in 2..limit
+    {
+        if composite[i]
+        {
+            continue;
+        }
+        primes.push(i as u32);
+        // Les multiples inférieurs à i * i ont déjà été barrés par un facteur plus petit
+        for multiple in (i.saturating_mul(i)..limit).step_by(i)
+        {
+            composite[multiple] = true;
+        }
+    }
+
+    primes
+}
+
+/// Retourne vrai si `n` est divisible par l'un des nombres premiers de `primes` (autre que lui-même).
+/// Utilisé pour écarter rapidement les candidats avant le test de Miller-Rabin.
+#[cfg(feature = "std")]
+pub(crate) fn divisible_by_small(n: &BigUint, primes: &[u32]) -> bool
+{
+    primes.iter().any(| &p | (n % p).is_zero() && *n != BigUint::from(p))
+}
+
+/// Retourne un candidat premier aléatoire, impair et de exactement `szb * 8` bits (les deux bits de poids fort étant à 1), tiré depuis le générateur `rng`.
+/// Un générateur initialisé avec une graine fixe (`StdRng::seed_from_u64`) donne des résultats reproductibles.
+///
+/// Les candidats servant à produire des clés, le générateur doit être cryptographiquement sûr (`CryptoRng`) :
+/// un générateur quelconque est refusé à la compilation.
+///
+/// ```compile_fail
+/// use rand::rngs::mock::StepRng;
+///
+/// let mut rng = StepRng::new(0, 1);
+/// rrsa::maths::rand_primelike(&mut rng, 32);
+/// ```
+pub fn rand_primelike<R: Rng + CryptoRng>(rng: &mut R, szb: u64) -> BigUint
+{
+    if szb == 0
+    {
+        return BigUint::zero();
+    }
+
+    let bits = szb * 8;
+    let mut b = rng.gen_biguint(bits);
+
+    // Les deux bits de poids fort sont mis à 1 : le nombre occupe exactement `szb * 8` bits,
+    // et le produit de deux tels nombres occupe exactement le double
+    b.set_bit(bits - 1, true);
+    b.set_bit(bits - 2, true);
+    // Un nombre premier (hors 2) est impair
+    b.set_bit(0, true);
+
+    b
+}
+
+/// Voir `rand_primelike`, avec le générateur du système d'exploitation (`OsRng`), qui puise directement dans l'entropie du système.
+#[cfg(feature = "std")]
+pub fn rand_primelike_def(szb: u64) -> BigUint
+{
+    rand_primelike(&mut rand::rngs::OsRng, szb)
+}
+
+/// Retourne un nombre premier (probable) d'exactement `bits` bits, tiré depuis le générateur `rng`. Chaque candidat a son bit de poids fort
+/// et son bit de poids faible à 1 ; ceux ayant un petit facteur (crible jusqu'à `SIEVE_LIMIT_DEF`) sont écartés avant le test
+/// de Miller-Rabin. Contrairement à `rand_primelike`, la taille est donnée en bits. Panique si `bits` est inférieur à 2, ou si aucun des
+/// `PRIME_ATTEMPTS_PER_BIT_DEF * bits` candidats n'est premier (générateur défaillant) : voir `try_rand_prime_bits` pour une version sans panique.
+#[cfg(feature = "std")]
+pub fn rand_prime_bits<R: Rng + CryptoRng>(rng: &mut R, bits: u64) -> BigUint
+{
+    try_rand_prime_bits(rng, bits, default_max_attempts(bits)).expect("maths.rand_prime_bits : nombre maximal de candidats atteint")
+}
+
+/// Voir `rand_prime_bits`, en tirant au plus `max_attempts` candidats : au-delà, retourne `MathError::ExhaustedAttempts`.
+/// Panique si `bits` est inférieur à 2.
+#[cfg(feature = "std")]
+pub fn try_rand_prime_bits<R: Rng + CryptoRng>(rng: &mut R, bits: u64, max_attempts: u64) -> Result<BigUint, MathError>
+{
+    assert!(bits >= 2, "maths.rand_prime_bits : un nombre premier impair occupe au moins 2 bits");
+
+    rand_prime_sieved(rng, bits, &small_prime_sieve(SIEVE_LIMIT_DEF), max_attempts)
+}
+
+/// Retourne un itérateur infini de nombres premiers (probables) d'exactement `bits` bits, tirés indépendamment depuis le générateur `rng`
+/// à chaque appel de `next` (voir `rand_prime_bits`), par exemple pour générer plusieurs clés ou étudier le générateur statistiquement.
+/// Le crible des petits nombres premiers n'est calculé qu'une fois. Panique si `bits` est inférieur à 2, ou comme `rand_prime_bits`
+/// si le nombre maximal de candidats est atteint : voir `try_prime_stream` pour une version sans panique.
+#[cfg(feature = "std")]
+pub fn prime_stream<R: Rng + CryptoRng>(rng: R, bits: u64) -> impl Iterator<Item = BigUint>
+{
+    try_prime_stream(rng, bits, default_max_attempts(bits)).map(| p | p.expect("maths.prime_stream : nombre maximal de candidats atteint"))
+}
+
+/// Voir `prime_stream`, chaque nombre premier étant cherché parmi au plus `max_attempts` candidats : l'itérateur produit
+/// `MathError::ExhaustedAttempts` pour chaque recherche infructueuse. Panique si `bits` est inférieur à 2.
+#[cfg(feature = "std")]
+pub fn try_prime_stream<R: Rng + CryptoRng>(mut rng: R, bits: u64, max_attempts: u64) -> impl Iterator<Item = Result<BigUint, MathError>>
+{
+    assert!(bits >= 2, "maths.prime_stream : un nombre premier impair occupe au moins 2 bits");
+
+    let sieve = small_prime_sieve(SIEVE_LIMIT_DEF);
+
+    core::iter::repeat_with(move || rand_prime_sieved(&mut rng, bits, &sieve, max_attempts))
+}
+
+/// Tire au plus `max_attempts` candidats de `bits` bits (bits de poids fort et faible à 1) jusqu'à trouver un nombre premier : ceux divisibles
+/// par l'un des petits nombres premiers `sieve` sont écartés avant le test de Miller-Rabin.
+#[cfg(feature = "std")]
+fn rand_prime_sieved<R: Rng + CryptoRng>(rng: &mut R, bits: u64, sieve: &[u32], max_attempts: u64) -> Result<BigUint, MathError>
+{
+    for _ in 0..max_attempts
+    {
+        let mut candidate = rng.gen_biguint(bits);
+        candidate.set_bit(bits - 1, true);
+        candidate.set_bit(0, true);
+
+        if !divisible_by_small(&candidate, sieve) && is_prime_rounds(&candidate, PRIME_ROUNDS_DEF)
+        {
+            return Ok(candidate);
+        }
+    }
+
+    Err(MathError::ExhaustedAttempts)
+}
+
+/// Nombre maximal de candidats examinés par défaut pour un nombre premier de `bits` bits (voir `PRIME_ATTEMPTS_PER_BIT_DEF`).
+#[cfg(feature = "std")]
+pub(crate) fn default_max_attempts(bits: u64) -> u64
+{
+    bits.max(1).saturating_mul(PRIME_ATTEMPTS_PER_BIT_DEF)
+}
+
+/// Retourne un nombre premier sûr `p = 2q + 1` (où `q` est aussi premier) de exactement `szb * 8` bits, tiré depuis le générateur `rng`.
+/// Les candidats sont tirés jusqu'à ce que `q` et `p` soient tous deux premiers ; ceux dont `q` ou `p` a un petit facteur sont écartés
+/// avant le test de Miller-Rabin. Cette génération est nettement plus lente que celle d'un nombre premier quelconque.
+/// Panique si `szb` est nul (aucun nombre premier sûr ne tient sur zéro octet), ou si aucun des `PRIME_ATTEMPTS_PER_BIT_DEF * szb * 8`
+/// candidats ne convient (générateur défaillant) : voir `try_rand_safe_prime` pour une version sans panique.
+#[cfg(feature = "std")]
+pub fn rand_safe_prime<R: Rng + CryptoRng>(rng: &mut R, szb: u64) -> BigUint
+{
+    try_rand_safe_prime(rng, szb, default_max_attempts(szb.saturating_mul(8))).expect("maths.rand_safe_prime : nombre maximal de candidats atteint")
+}
+
+/// Voir `rand_safe_prime`, en tirant au plus `max_attempts` candidats : au-delà, retourne `MathError::ExhaustedAttempts`.
+/// Panique si `szb` est nul.
+#[cfg(feature = "std")]
+pub fn try_rand_safe_prime<R: Rng + CryptoRng>(rng: &mut R, szb: u64, max_attempts: u64) -> Result<BigUint, MathError>
+{
+    assert!(szb >= 1, "maths.rand_safe_prime : un nombre premier sûr occupe au moins un octet");
+
+    let sieve = small_prime_sieve(SIEVE_LIMIT_DEF);
+
+    for _ in 0..max_attempts
+    {
+        // q a un bit de moins que p ; ses deux bits de poids fort restent à 1, de même que ceux de p
+        let mut q = rand_primelike(rng, szb) >> 1u8;
+        q.set_bit(0, true);
+        let p = (&q << 1u8) + 1u8;
+
+        if divisible_by_small(&q, &sieve) || divisible_by_small(&p, &sieve)
+        {
+            continue;
+        }
+        if isprime(&q) && isprime(&p)
+        {
+            return Ok(p);
+        }
+    }
+
+    Err(MathError::ExhaustedAttempts)
+}
+
+/// Retourne un nombre premier (probable) tiré uniformément parmi les candidats de l'intervalle `[low, high)`, depuis le générateur `rng`.
+/// Utile pour contraindre la taille exacte du produit de deux nombres premiers. Si aucun des `RANGE_PRIME_DRAWS` tirages n'est premier,
+/// l'intervalle est parcouru depuis `low` : le premier nombre premier trouvé est retourné, ou une erreur s'il n'y en a aucun.
+#[cfg(feature = "std")]
+pub fn rand_prime_in_range<R: Rng + CryptoRng>(rng: &mut R, low: &BigUint, high: &BigUint) -> Result<BigUint, MathError>
+{
+    if low >= high
+    {
+        return Err(MathError::NoPrimeInRange);
+    }
+
+    let sieve = small_prime_sieve(SIEVE_LIMIT_DEF);
+    for _ in 0..RANGE_PRIME_DRAWS
+    {
+        let candidate = rng.gen_biguint_range(low, high);
+        if !divisible_by_small(&candidate, &sieve) && isprime(&candidate)
+        {
+            return Ok(candidate);
+        }
+    }
+
+    // next_prime retourne un nombre strictement supérieur à son argument : on part de low - 1 pour inclure low
+    let start = if low.is_zero() { low.clone() } else { low - 1u8 };
+    let first = try_next_prime(&start, default_max_attempts(start.bits()))?;
+    if first < *high
+    {
+        Ok(first)
+    }
+    else
+    {
+        Err(MathError::NoPrimeInRange)
+    }
+}
+
+/// Retourne un facteur non trivial de `n` par la méthode rho de Pollard (itération `x ↦ x^2 + c mod n`, détection de cycle de Floyd).
+/// Retourne `2` si `n` est pair, et `None` si `n` est premier ou inférieur à 4. Si un cycle ne donne que `n` lui-même,
+/// la recherche recommence avec la constante `c` suivante. Utile pour casser de petits modules ou tester la robustesse d'une clé.
+#[cfg(feature = "std")]
+pub fn pollard_rho(n: &BigUint) -> Option<BigUint>
+{
+    if *n < BigUint::from(4u8) || is_prime_rounds(n, PRIME_ROUNDS_DEF)
+    {
+        return None;
+    }
+    if !n.bit(0)
+    {
+        return Some(BigUint::from(2u8));
+    }
+
+    let mut c = BigUint::one();
+    loop
+    {
+        let step = | x: &BigUint | (x * x + &c) % n;
+        let (mut x, mut y) = (BigUint::from(2u8), BigUint::from(2u8));
+        let mut d = BigUint::one();
+
+        while d.is_one()
+        {
+            x = step(&x);
+            y = step(&step(&y));
+            d = gcd(&if x > y { &x - &y } else { &y - &x }, n);
+        }
+
+        if d != *n
+        {
+            return Some(d);
+        }
+        c += 1u8;
+    }
+}
+
+/// Retourne la plus petite racine primitive modulo le nombre premier `p`, c'est-à-dire un générateur du groupe multiplicatif `(Z/pZ)*`,
+/// d'ordre `p - 1` (utile pour Diffie-Hellman ou ElGamal). `p - 1` est factorisé par `pollard_rho`, puis `g = 2, 3, ...` est retenu dès que
+/// `g^((p - 1) / q) != 1 mod p` pour chacun de ses facteurs premiers `q`. Retourne `None` si `p` n'est pas premier.
+/// La factorisation de `p - 1` peut être très longue pour un grand `p` quelconque (contrairement à un nombre premier sûr, voir `rand_safe_prime`).
+#[cfg(feature = "std")]
+pub fn primitive_root(p: &BigUint) -> Option<BigUint>
+{
+    if !isprime(p)
+    {
+        return None;
+    }
+
+    let pm1 = p - 1u8;
+    let factors = distinct_prime_factors(&pm1);
+    let mut g = BigUint::one();
+
+    loop
+    {
+        g += 1u8;
+        if g >= *p
+        {
+            // Seul p = 2 y arrive : 1 engendre alors le groupe trivial {1}
+            return Some(BigUint::one());
+        }
+        if factors.iter().all(| q | !fmodpow(&g, &(&pm1 / q), p).is_one())
+        {
+            return Some(g);
+        }
+    }
+}
+
+/// Facteurs premiers distincts de `n`, par décompositions successives avec `pollard_rho` (vide pour `n <= 1`).
+#[cfg(feature = "std")]
+fn distinct_prime_factors(n: &BigUint) -> Vec<BigUint>
+{
+    let (mut pending, mut factors) = (vec![n.clone()], Vec::new());
+
+    while let Some(m) = pending.pop()
+    {
+        if m <= BigUint::one()
+        {
+            continue;
+        }
+        match pollard_rho(&m)
+        {
+            Some(d) => {
+                pending.push(&m / &d);
+                pending.push(d);
+            },
+            None if !factors.contains(&m) => factors.push(m),
+            None => {}
+        }
+    }
+
+    factors
+}
+
+/// Factorisation de Fermat : cherche `a` tel que `a^2 - n` soit un carré parfait `b^2`, d'où `n = (a - b)(a + b)`.
+/// Particulièrement rapide lorsque les deux facteurs sont proches (faiblesse classique d'une clé RSA dont p et q sont trop proches) :
+/// `a` part de `⌈√n⌉` et au plus `max_iters` valeurs sont essayées. Retourne les deux facteurs non triviaux (le plus petit en premier),
+/// ou `None` si `n` est pair, inférieur à 2, ou si aucun facteur n'a été trouvé.
+pub fn fermat_factor(n: &BigUint, max_iters: u64) -> Option<(BigUint, BigUint)>
+{
+    if !n.bit(0) || n.is_one()
+    {
+        return None;
+    }
+
+    let mut a = isqrt(n);
+    if &a * &a < *n
+    {
+        a += 1u8;
+    }
+
+    for _ in 0..max_iters
+    {
+        let b2 = &a * &a - n;
+        let b = isqrt(&b2);
+        if &b * &b == b2
+        {
+            let (p, q) = (&a - &b, &a + &b);
+            // a - b == 1 correspond à la factorisation triviale 1 * n : n est premier
+            return if p.is_one() { None } else { Some((p, q)) };
+        }
+        a += 1u8;
+    }
+
+    None
+}
+
+/// Attaque de Wiener : retrouve un exposant privé `d` trop petit (`d < n^(1/4) / 3`) depuis la clé publique (`n`, `e`).
+/// Chaque réduite `k / d` du développement en fraction continue de `e / n` est essayée : `phi = (e * d - 1) / k` doit alors être entier
+/// et les racines de `x^2 - (n - phi + 1) x + n` doivent être les deux facteurs de `n`. Retourne `d` si l'attaque aboutit, `None` sinon.
+/// Permet de vérifier qu'une clé importée ne possède pas un exposant privé vulnérable.
+pub fn wiener_attack(n: &BigUint, e: &BigUint) -> Option<BigUint>
+{
+    let (mut num, mut den) = (e.clone(), n.clone());
+    // Numérateurs (k) et dénominateurs (d) des deux réduites précédentes
+    let (mut k_prev, mut k) = (BigUint::zero(), BigUint::one());
+    let (mut d_prev, mut d) = (BigUint::one(), BigUint::zero());
+
+    while !den.is_zero()
+    {
+        let a = &num / &den;
+        let next_k = &a * &k + &k_prev;
+        let next_d = &a * &d + &d_prev;
+        k_prev = core::mem::replace(&mut k, next_k);
+        d_prev = core::mem::replace(&mut d, next_d);
+        let rem = &num % &den;
+        num = core::mem::replace(&mut den, rem);
+
+        if k.is_zero()
+        {
+            continue;
+        }
+        let ed1 = e * &d - 1u8;
+        if !(&ed1 % &k).is_zero()
+        {
+            continue;
+        }
+
+        // Somme des facteurs p + q = n - phi + 1, puis discriminant (p - q)^2 = (p + q)^2 - 4n
+        let phi = ed1 / &k;
+        if phi >= *n
+        {
+            continue;
+        }
+        let s = n - phi + 1u8;
+        let (s2, n4) = (&s * &s, n << 2u32);
+        if s2 < n4
+        {
+            continue;
+        }
+        let delta = s2 - n4;
+        let diff = isqrt(&delta);
+        // p = (s - diff) / 2 et q = (s + diff) / 2 sont alors entiers, de produit n : p doit être un facteur non trivial
+        if &diff * &diff == delta && !(&s + &diff).bit(0) && (&s - &diff) > BigUint::from(2u8)
+        {
+            return Some(d);
+        }
+    }
+
+    None
 }
\ No newline at end of file
@@ -1,12 +1,176 @@
 use std::string::FromUtf8Error;
 use num_bigint::BigUint;
-use num_traits::{Num, ToPrimitive};
-use crate::{maths::{NumUtil, VecNumUtil}, engines};
+use num_traits::{Num, ToPrimitive, Zero};
+use crate::{maths::{MathError, NumUtil, VecNumUtil}, engines};
 
 /// Base (radix) à utiliser pour la transformation d'un message en sa version textuelle numérique (`to_nstr`).
 const NUM_STRING_RADIX: u32 = 36;
 /// Séparateur à utiliser lorsque le message est transformé en la représentation textuelle de ses parties (`to_parts_str`)
 const PARTS_STR_SEP: &str = ":";
+/// Chiffres utilisés par `to_radix_string` / `from_radix_string` : chiffres décimaux, puis minuscules, puis majuscules (jusqu'à la base 62).
+const RADIX_ALPHABET: &[u8; 62] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+
+/// Ordre des octets des conversions entre octets et grands entiers (`bytes_to_biguint_with`, `biguint_to_bytes_with`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteOrder
+{
+    /// Gros-boutiste : l'octet de poids fort en premier (ordre de PKCS#1, utilisé par défaut dans la bibliothèque).
+    Be,
+    /// Petit-boutiste : l'octet de poids faible en premier (ordre de `BigUint::from_bytes_le`).
+    Le
+}
+
+
+/// Convertit des octets gros-boutistes en grand entier, sans passer par un découpage en parties.
+/// Les octets nuls de tête sont sans effet sur la valeur ; une tranche vide donne zéro.
+pub fn bytes_to_biguint(bytes: &[u8]) -> BigUint
+{
+    bytes_to_biguint_with(bytes, ByteOrder::Be)
+}
+
+/// Voir `bytes_to_biguint`, dans l'ordre d'octets `order` (en petit-boutiste, ce sont les octets nuls de fin qui sont sans effet).
+pub fn bytes_to_biguint_with(bytes: &[u8], order: ByteOrder) -> BigUint
+{
+    match order
+    {
+        ByteOrder::Be => BigUint::from_bytes_be(bytes),
+        ByteOrder::Le => BigUint::from_bytes_le(bytes)
+    }
+}
+
+/// Convertit un grand entier en octets gros-boutistes sur exactement `len` octets, complétés par des zéros en tête.
+/// Si `n` ne tient pas sur `len` octets, tous ses octets sont retournés (le résultat est alors plus long que `len`).
+pub fn biguint_to_bytes(n: &BigUint, len: usize) -> Vec<u8>
+{
+    biguint_to_bytes_with(n, len, ByteOrder::Be)
+}
+
+/// Voir `biguint_to_bytes`, dans l'ordre d'octets `order` : en petit-boutiste, les zéros de complément sont ajoutés en fin.
+pub fn biguint_to_bytes_with(n: &BigUint, len: usize, order: ByteOrder) -> Vec<u8>
+{
+    if n.is_zero()
+    {
+        return vec![0u8; len];
+    }
+
+    match order
+    {
+        ByteOrder::Be => {
+            let bytes = n.to_bytes_be();
+            let mut out = vec![0u8; len.saturating_sub(bytes.len())];
+            out.extend(bytes);
+            out
+        },
+        ByteOrder::Le => {
+            let mut out = n.to_bytes_le();
+            if out.len() < len
+            {
+                out.resize(len, 0u8);
+            }
+            out
+        }
+    }
+}
+
+/// Convertit un grand entier en sa représentation textuelle dans une base `radix` comprise entre 2 et 62 (voir `RADIX_ALPHABET`).
+/// Jusqu'à la base 36, le résultat est identique à `BigUint::to_str_radix`. Panique si la base est hors de cet intervalle.
+pub fn to_radix_string(n: &BigUint, radix: u32) -> String
+{
+    assert!((2..=62).contains(&radix), "to_radix_string : base {} non comprise entre 2 et 62", radix);
+
+    n.to_radix_be(radix).iter().map(| &digit | RADIX_ALPHABET[usize::from(digit)] as char).collect()
+}
+
+/// Lit un grand entier depuis sa représentation textuelle dans une base `radix` comprise entre 2 et 62 (inverse de `to_radix_string`).
+/// Jusqu'à la base 36, les majuscules sont acceptées comme leurs équivalents minuscules.
+pub fn from_radix_string(s: &str, radix: u32) -> Result<BigUint, MathError>
+{
+    if !(2..=62).contains(&radix)
+    {
+        return Err(MathError::InvalidRadix(radix));
+    }
+    if s.is_empty()
+    {
+        return Err(MathError::InvalidDigit);
+    }
+
+    let digits = s.bytes().map(| c | {
+        let c = if radix <= 36 { c.to_ascii_lowercase() } else { c };
+        match RADIX_ALPHABET.iter().position(| &a | a == c)
+        {
+            Some(digit) if digit < radix as usize => Ok(digit as u8),
+            _ => Err(MathError::InvalidDigit)
+        }
+    }).collect::<Result<Vec<u8>, MathError>>()?;
+
+    BigUint::from_radix_be(&digits, radix).ok_or(MathError::InvalidDigit)
+}
+
+/// Convertit un grand entier en hexadécimal minuscule, sans préfixe `0x` (zéro donne `"0"`).
+pub fn to_hex(n: &BigUint) -> String
+{
+    n.to_str_radix(16)
+}
+
+/// Lit un grand entier hexadécimal (inverse de `to_hex`). Les espaces autour de la valeur, un préfixe `0x` (ou `0X`)
+/// et les majuscules sont acceptés ; une chaîne vide ou un caractère non hexadécimal donne `MathError::InvalidDigit`.
+pub fn from_hex(s: &str) -> Result<BigUint, MathError>
+{
+    let s = s.trim();
+    let s = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+
+    from_radix_string(s, 16)
+}
+
+/// Bloc brut de clair ou de chiffré pour un module `n` donné, de la taille du module en octets (`n.sz_b()`).
+/// Contrairement à `Message`, aucun découpage n'est effectué : un bloc trop long ou dont la valeur atteint `n` est refusé à la construction
+/// (`new`) au lieu d'être tronqué silencieusement lors de l'opération RSA, et sa conversion en octets (`Vec<u8>`) est toujours
+/// de la taille du module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawMessage
+{
+    value: BigUint,
+    modulus_len: usize
+}
+
+impl RawMessage
+{
+    /// Lit un bloc gros-boutiste destiné au module `n` (celui d'une clé générée ou importée, par exemple) ; retourne `MathError::TooLarge`
+    /// s'il fait plus d'octets que le module, octets nuls de tête compris, ou si sa valeur est supérieure ou égale à `n`.
+    pub fn new(bytes: &[u8], n: &BigUint) -> Result<Self, MathError>
+    {
+        let modulus_len = n.sz_b() as usize;
+        let value = bytes_to_biguint(bytes);
+        if bytes.len() > modulus_len || value >= *n
+        {
+            return Err(MathError::TooLarge);
+        }
+
+        Ok(RawMessage { value, modulus_len })
+    }
+
+    /// Valeur numérique du bloc.
+    pub fn value(&self) -> &BigUint
+    {
+        &self.value
+    }
+
+    /// Taille du module en octets, et donc de la conversion du bloc en octets.
+    pub fn modulus_len(&self) -> usize
+    {
+        self.modulus_len
+    }
+}
+
+impl From<RawMessage> for Vec<u8>
+{
+    fn from(msg: RawMessage) -> Self
+    {
+        biguint_to_bytes(&msg.value, msg.modulus_len)
+    }
+}
+
 
 /// Structure du builder pattern permettant de construire un message avec des arguments optionnels
 pub struct MessageBuilder
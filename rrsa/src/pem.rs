@@ -0,0 +1,361 @@
+use crate::{base64, der::{self, DerError}, messages, rsa::{self, CrtParams, KeyError, KeyPair, OtherPrime, PublicKey}};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::{error::Error, fmt::Display, fs, io::{self, Write}, path::Path, str::FromStr};
+
+
+/// Permissions Unix du fichier de clé privée écrit par `KeyPair::save_pem` : lecture et écriture par le seul propriétaire.
+#[cfg(unix)]
+const PRIVATE_KEY_MODE: u32 = 0o600;
+/// Étiquette PEM d'une clé publique RSA au format PKCS#1.
+const PEM_PUBLIC_LABEL: &str = "RSA PUBLIC KEY";
+/// Étiquette PEM d'une clé privée RSA au format PKCS#1.
+const PEM_PRIVATE_LABEL: &str = "RSA PRIVATE KEY";
+/// Nombre de caractères base64 par ligne dans un bloc PEM.
+const PEM_LINE_LEN: usize = 64;
+
+
+/// Erreurs pouvant survenir lors de la lecture d'une clé au format PEM.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PemError
+{
+    /// Les lignes d'encadrement `-----BEGIN ...-----` / `-----END ...-----` sont absentes ou incohérentes.
+    MissingArmor,
+    /// Le contenu du bloc n'est pas du base64 valide.
+    InvalidBase64,
+    /// Le contenu décodé n'est pas un encodage DER valide.
+    Der(DerError),
+    /// La structure DER ne correspond pas à une clé RSA.
+    InvalidStructure,
+    /// Le bloc PEM n'est pas une clé RSA PKCS#1 (`RSA PRIVATE KEY` / `RSA PUBLIC KEY`) : son étiquette est donnée.
+    UnsupportedLabel(String),
+    /// La chaîne lue par `PublicKey::from_str` n'est ni un bloc PEM ni une paire `n:e` hexadécimale.
+    InvalidComponents,
+    /// La clé privée lue est incohérente (voir `KeyPair::validate`).
+    InvalidKey(KeyError)
+}
+
+impl Display for PemError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            PemError::MissingArmor => write!(f, "Impossible de lire le bloc PEM (PemError) : encadrement BEGIN / END absent"),
+            PemError::InvalidBase64 => write!(f, "Impossible de lire le bloc PEM (PemError) : base64 invalide"),
+            PemError::Der(e) => write!(f, "Impossible de lire le bloc PEM (PemError) : {}", e),
+            PemError::InvalidStructure => write!(f, "Impossible de lire le bloc PEM (PemError) : structure de clé RSA invalide"),
+            PemError::UnsupportedLabel(label) => write!(f, "Impossible de lire le bloc PEM (PemError) : bloc `{}` non RSA PKCS#1", label),
+            PemError::InvalidComponents => write!(f, "Impossible de lire la clé (PemError) : ni bloc PEM, ni paire `n:e` hexadécimale"),
+            PemError::InvalidKey(e) => write!(f, "Impossible de lire le bloc PEM (PemError) : {}", e)
+        }
+    }
+}
+
+impl Error for PemError {}
+
+impl From<DerError> for PemError
+{
+    fn from(e: DerError) -> Self
+    {
+        PemError::Der(e)
+    }
+}
+
+impl From<KeyError> for PemError
+{
+    fn from(e: KeyError) -> Self
+    {
+        PemError::InvalidKey(e)
+    }
+}
+
+
+/// Erreurs pouvant survenir lors du chargement d'une clé depuis un fichier PEM (`KeyPair::load_pem`).
+#[derive(Debug)]
+pub enum LoadError
+{
+    /// Le fichier n'a pas pu être lu.
+    Io(io::Error),
+    /// Le contenu du fichier n'est pas une clé PEM valide.
+    Pem(PemError)
+}
+
+impl Display for LoadError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            LoadError::Io(e) => write!(f, "Impossible de charger la clé (LoadError) : {}", e),
+            LoadError::Pem(e) => write!(f, "Impossible de charger la clé (LoadError) : {}", e)
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+impl From<io::Error> for LoadError
+{
+    fn from(e: io::Error) -> Self
+    {
+        LoadError::Io(e)
+    }
+}
+
+impl From<PemError> for LoadError
+{
+    fn from(e: PemError) -> Self
+    {
+        LoadError::Pem(e)
+    }
+}
+
+
+impl KeyPair
+{
+    /// Exporte la partie publique de la clé au format PEM PKCS#1 (`RSAPublicKey ::= SEQUENCE { n, e }`).
+    pub fn to_pem_public(&self) -> String
+    {
+        let der = der::encode_sequence(&[der::encode_integer(&self.n), der::encode_integer(&self.e)]);
+        pem_armor(PEM_PUBLIC_LABEL, &der)
+    }
+
+    /// Exporte la clé privée au format PEM PKCS#1 (`RSAPrivateKey`) : version 0 pour une clé à deux nombres premiers,
+    /// version 1 suivie des `otherPrimeInfos` pour une clé multi-premiers.
+    /// Si les paramètres CRT sont absents, les champs correspondants sont écrits à zéro.
+    pub fn to_pem_private(&self) -> String
+    {
+        let zero = BigUint::zero();
+        let (p, q, dp, dq, qinv) = match &self.crt
+        {
+            Some(crt) => (&crt.p, &crt.q, &crt.dp, &crt.dq, &crt.qinv),
+            None => (&zero, &zero, &zero, &zero, &zero)
+        };
+        let others = self.crt.as_ref().map_or(&[][..], | crt | &crt.others[..]);
+        let version = BigUint::from(u8::from(!others.is_empty()));
+
+        let mut parts = vec![
+            der::encode_integer(&version),
+            der::encode_integer(&self.n),
+            der::encode_integer(&self.e),
+            der::encode_integer(&self.d),
+            der::encode_integer(p),
+            der::encode_integer(q),
+            der::encode_integer(dp),
+            der::encode_integer(dq),
+            der::encode_integer(qinv)
+        ];
+        if !others.is_empty()
+        {
+            let infos: Vec<Vec<u8>> = others.iter().map(| o | {
+                der::encode_sequence(&[der::encode_integer(&o.r), der::encode_integer(&o.d), der::encode_integer(&o.t)])
+            }).collect();
+            parts.push(der::encode_sequence(&infos));
+        }
+
+        pem_armor(PEM_PRIVATE_LABEL, &der::encode_sequence(&parts))
+    }
+
+    /// Importe une clé privée depuis un bloc PEM PKCS#1 (`RSA PRIVATE KEY`, tel que produit par `openssl genrsa -traditional`),
+    /// y compris une clé multi-premiers (version 1). Les paramètres CRT sont renseignés si les nombres premiers sont présents (non nuls).
+    /// Un bloc d'une autre nature (clé publique `RSA PUBLIC KEY`, clé PKCS#8 `PRIVATE KEY`, certificat, etc) est refusé avec
+    /// `PemError::UnsupportedLabel`, et une clé incohérente (voir `KeyPair::validate`) avec `PemError::InvalidKey`.
+    pub fn from_pem(pem: &str) -> Result<Self, PemError>
+    {
+        let (label, der) = pem_unarmor(pem)?;
+        if label != PEM_PRIVATE_LABEL
+        {
+            return Err(PemError::UnsupportedLabel(label));
+        }
+        let (mut rest, used) = der::decode_sequence(&der)?;
+        if used != der.len()
+        {
+            return Err(PemError::InvalidStructure);
+        }
+
+        // Les neuf entiers de la version 0
+        let mut ints = Vec::with_capacity(9);
+        while ints.len() < 9 && !rest.is_empty()
+        {
+            let (n, used) = der::decode_integer(rest)?;
+            ints.push(n);
+            rest = &rest[used..];
+        }
+        if ints.len() != 9 || ints[0] > BigUint::one()
+        {
+            return Err(PemError::InvalidStructure);
+        }
+
+        // Version 1 : séquence des facteurs supplémentaires `OtherPrimeInfo ::= SEQUENCE { prime, exponent, coefficient }`
+        let mut others = Vec::new();
+        if !rest.is_empty()
+        {
+            let (mut infos, used) = der::decode_sequence(rest)?;
+            if used != rest.len() || !ints[0].is_one()
+            {
+                return Err(PemError::InvalidStructure);
+            }
+            while !infos.is_empty()
+            {
+                let (_, used) = der::decode_sequence(infos)?;
+                let info = der::decode_integer_sequence(&infos[..used])?;
+                if info.len() != 3
+                {
+                    return Err(PemError::InvalidStructure);
+                }
+                let mut info = info.into_iter();
+                let mut next = || info.next().unwrap();
+                others.push(OtherPrime { r: next(), d: next(), t: next() });
+                infos = &infos[used..];
+            }
+        }
+
+        let mut ints = ints.into_iter().skip(1);
+        let mut next = || ints.next().unwrap();
+        let (n, e, d) = (next(), next(), next());
+        let (p, q, dp, dq, qinv) = (next(), next(), next(), next(), next());
+        let crt = if p.is_zero() || q.is_zero() { None } else { Some(CrtParams { p, q, dp, dq, qinv, others }) };
+
+        let key = KeyPair { n, e, d, crt };
+        key.validate()?;
+
+        Ok(key)
+    }
+
+    /// Écrit la clé privée au format PEM (voir `to_pem_private`) dans le fichier `path`, créé ou remplacé.
+    /// Sous Unix, le fichier est restreint à son propriétaire (`PRIVATE_KEY_MODE`, soit 0600) avant que la clé n'y soit écrite,
+    /// y compris lorsqu'il existait déjà avec des permissions plus larges.
+    pub fn save_pem<P: AsRef<Path>>(&self, path: P) -> io::Result<()>
+    {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+            // Le mode de création ne s'applique qu'à un nouveau fichier : un fichier existant est restreint avant d'être tronqué
+            options.mode(PRIVATE_KEY_MODE);
+            if path.as_ref().exists()
+            {
+                fs::set_permissions(&path, fs::Permissions::from_mode(PRIVATE_KEY_MODE))?;
+            }
+        }
+
+        let mut file = options.open(&path)?;
+        file.write_all(self.to_pem_private().as_bytes())?;
+        file.sync_all()
+    }
+
+    /// Charge une clé privée depuis le fichier PEM `path` (voir `from_pem`).
+    pub fn load_pem<P: AsRef<Path>>(path: P) -> Result<Self, LoadError>
+    {
+        Ok(KeyPair::from_pem(&fs::read_to_string(path)?)?)
+    }
+}
+
+/// Lecture d'une clé privée depuis une chaîne par `str::parse` : voir `KeyPair::from_pem`.
+impl FromStr for KeyPair
+{
+    type Err = PemError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        KeyPair::from_pem(s)
+    }
+}
+
+
+impl PublicKey
+{
+    /// Importe une clé publique depuis un bloc PEM PKCS#1 `RSA PUBLIC KEY` (tel que produit par `KeyPair::to_pem_public`),
+    /// ou extrait la partie publique d'un bloc `RSA PRIVATE KEY`. Un module inférieur à 256 est refusé (`PemError::InvalidKey`).
+    pub fn from_pem(pem: &str) -> Result<Self, PemError>
+    {
+        let (label, der) = pem_unarmor(pem)?;
+        if label == PEM_PRIVATE_LABEL
+        {
+            return KeyPair::from_pem(pem).map(| k | k.public_key());
+        }
+        if label != PEM_PUBLIC_LABEL
+        {
+            return Err(PemError::UnsupportedLabel(label));
+        }
+
+        let (_, used) = der::decode_sequence(&der)?;
+        let ints = der::decode_integer_sequence(&der)?;
+        if used != der.len() || ints.len() != 2
+        {
+            return Err(PemError::InvalidStructure);
+        }
+        let mut ints = ints.into_iter();
+        let (n, e) = (ints.next().unwrap(), ints.next().unwrap());
+        rsa::check_modulus(&n)?;
+
+        Ok(PublicKey { n, e })
+    }
+}
+
+/// Lecture d'une clé publique depuis une chaîne par `str::parse` : un bloc PEM (reconnu à sa ligne `-----BEGIN`, voir `PublicKey::from_pem`),
+/// ou sinon une paire `n:e` de nombres en base 16, et non en base 10 (voir `messages::from_hex`) : `3233:11` désigne `n = 0x3233`
+/// et `e = 0x11`. Le module doit être d'au moins 256 (`KeyError::ModulusTooSmall`) et l'exposant impair, avec `1 < e < n`
+/// (`KeyError::InvalidExponent`), ces erreurs étant données par `PemError::InvalidKey`.
+impl FromStr for PublicKey
+{
+    type Err = PemError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        if s.trim_start().starts_with("-----BEGIN")
+        {
+            return PublicKey::from_pem(s);
+        }
+
+        let mut parts = s.trim().splitn(2, ':');
+        match (parts.next().map(messages::from_hex), parts.next().map(messages::from_hex))
+        {
+            (Some(Ok(n)), Some(Ok(e))) => {
+                rsa::check_modulus(&n)?;
+                rsa::check_exponent(&e, &n)?;
+                Ok(PublicKey { n, e })
+            },
+            _ => Err(PemError::InvalidComponents)
+        }
+    }
+}
+
+
+/// Encadre des données DER par les lignes PEM de l'étiquette donnée, en base64 sur des lignes de 64 caractères.
+fn pem_armor(label: &str, der: &[u8]) -> String
+{
+    let b64 = base64::base64_encode(der);
+    let mut pem = format!("-----BEGIN {}-----\n", label);
+
+    for line in b64.as_bytes().chunks(PEM_LINE_LEN)
+    {
+        pem.push_str(std::str::from_utf8(line).unwrap());
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {}-----\n", label));
+
+    pem
+}
+
+/// Retire l'encadrement PEM et retourne l'étiquette du bloc et les données DER décodées.
+/// Les lignes `BEGIN` et `END` doivent porter la même étiquette.
+fn pem_unarmor(pem: &str) -> Result<(String, Vec<u8>), PemError>
+{
+    let lines: Vec<&str> = pem.lines().map(| l | l.trim()).filter(| l | !l.is_empty()).collect();
+    let begin = lines.iter().position(| l | l.starts_with("-----BEGIN ")).ok_or(PemError::MissingArmor)?;
+    let end = lines.iter().position(| l | l.starts_with("-----END ")).ok_or(PemError::MissingArmor)?;
+    let label = | line: &str, prefix: &str | line.strip_prefix(prefix).and_then(| l | l.strip_suffix("-----")).map(String::from);
+    let begin_label = label(lines[begin], "-----BEGIN ").ok_or(PemError::MissingArmor)?;
+
+    if end <= begin || label(lines[end], "-----END ").as_ref() != Some(&begin_label)
+    {
+        return Err(PemError::MissingArmor);
+    }
+
+    let der = base64::base64_decode(&lines[(begin + 1)..end].concat()).map_err(| _ | PemError::InvalidBase64)?;
+    Ok((begin_label, der))
+}
\ No newline at end of file
@@ -0,0 +1,389 @@
+use crate::{der, hash};
+use rand::Rng;
+use alloc::{vec, vec::Vec};
+use core::{error::Error, fmt::Display};
+
+
+/// Taille minimale en octets de la chaîne de remplissage aléatoire (PS) du padding PKCS#1 v1.5.
+const PKCS1_MIN_PS: usize = 8;
+/// Nombre d'octets ajoutés par le padding de chiffrement PKCS#1 v1.5 : un message fait au plus `k - PKCS1_ENCRYPT_OVERHEAD` octets.
+pub const PKCS1_ENCRYPT_OVERHEAD: usize = PKCS1_MIN_PS + 3;
+/// Octet final d'un encodage EMSA-PSS.
+const PSS_TRAILER: u8 = 0xbc;
+
+
+/// Fonction de hachage utilisable par MGF1 et OAEP : prend des données et retourne leur empreinte.
+pub type HashFn = fn(&[u8]) -> Vec<u8>;
+
+
+/// Erreurs pouvant survenir lors de l'ajout ou du retrait d'un padding.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PadError
+{
+    /// Le message est trop long pour la taille de module indiquée.
+    MessageTooLong,
+    /// Le bloc à dépadder n'a pas un format valide.
+    InvalidPadding
+}
+
+impl Display for PadError
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result
+    {
+        match self
+        {
+            PadError::MessageTooLong => write!(f, "Erreur de padding (PadError) : message trop long pour la taille du module"),
+            PadError::InvalidPadding => write!(f, "Erreur de padding (PadError) : padding invalide")
+        }
+    }
+}
+
+impl Error for PadError {}
+
+
+/// Applique le padding de chiffrement PKCS#1 v1.5 à un message : `0x00 0x02 PS 0x00 msg`, où PS est composé d'au moins 8 octets aléatoires non nuls.
+/// `k` est la taille en octets du module ; le message ne doit donc pas dépasser `k - 11` octets.
+pub fn pad_pkcs1_encrypt(msg: &[u8], k: usize, rng: &mut impl Rng) -> Result<Vec<u8>, PadError>
+{
+    if msg.len() + PKCS1_ENCRYPT_OVERHEAD > k
+    {
+        return Err(PadError::MessageTooLong);
+    }
+
+    let mut em = Vec::with_capacity(k);
+    em.push(0x00);
+    em.push(0x02);
+    // PS est aussi long que possible, et ne doit contenir aucun octet nul (sinon le séparateur serait ambigu)
+    for _ in 0..(k - msg.len() - 3)
+    {
+        em.push(rng.gen_range(1..=255u8));
+    }
+    em.push(0x00);
+    em.extend_from_slice(msg);
+
+    Ok(em)
+}
+
+/// Retire le padding de chiffrement PKCS#1 v1.5 d'un bloc déchiffré (de la taille du module) et retourne le message.
+/// Le bloc doit commencer par `0x00 0x02`, suivi d'au moins 8 octets non nuls puis d'un séparateur nul.
+/// Toutes les vérifications sont faites sur l'ensemble du bloc avant le seul branchement final (voir `scan_pkcs1_decrypt`),
+/// afin que le temps de traitement ne révèle pas quel octet invalide le padding (oracle de Bleichenbacher).
+pub fn unpad_pkcs1_decrypt(em: &[u8]) -> Result<Vec<u8>, PadError>
+{
+    // La taille du bloc, celle du module, est publique : ce rejet ne révèle rien du contenu
+    if em.len() < PKCS1_MIN_PS + 3
+    {
+        return Err(PadError::InvalidPadding);
+    }
+
+    match scan_pkcs1_decrypt(em)
+    {
+        (true, sep) => Ok(em[(sep + 1)..].to_vec()),
+        (false, _) => Err(PadError::InvalidPadding)
+    }
+}
+
+/// Parcourt un bloc d'au moins `PKCS1_MIN_PS + 3` octets sans sortie anticipée ni branchement sur son contenu, et retourne
+/// `(valide, position du séparateur)`. Chaque vérification (en-tête, présence du séparateur, longueur de PS) est cumulée
+/// dans un unique booléen ; la position du premier octet nul après l'en-tête est retenue par masque (0 s'il n'y en a aucun),
+/// et est donc calculée même lorsque l'en-tête est invalide.
+pub(crate) fn scan_pkcs1_decrypt(em: &[u8]) -> (bool, usize)
+{
+    let mut valid = (em[0] == 0x00) & (em[1] == 0x02);
+    let (mut looking, mut sep) = (true, 0usize);
+
+    for (i, &b) in em.iter().enumerate().skip(2)
+    {
+        let first_zero = core::hint::black_box(looking & (b == 0x00));
+        let mask = 0usize.wrapping_sub(usize::from(first_zero));
+        sep = (i & mask) | (sep & !mask);
+        looking &= b != 0x00;
+    }
+
+    valid &= !looking;
+    valid &= sep >= PKCS1_MIN_PS + 2;
+
+    (valid, sep)
+}
+
+
+/// Construit la structure DER `DigestInfo ::= SEQUENCE { SEQUENCE { OID, NULL }, OCTET STRING digest }` d'une empreinte.
+/// `hash_oid` est le contenu DER de l'OID de la fonction de hachage (par exemple `hash::SHA256_OID`).
+pub fn digest_info(digest: &[u8], hash_oid: &[u8]) -> Vec<u8>
+{
+    let algorithm = der::encode_sequence(&[der::encode_oid(hash_oid), der::encode_null()]);
+    der::encode_sequence(&[algorithm, der::encode_octet_string(digest)])
+}
+
+/// Extrait l'empreinte d'une structure DER `DigestInfo` ; l'identifiant d'algorithme n'est pas interprété.
+pub fn digest_info_digest(info: &[u8]) -> Result<&[u8], PadError>
+{
+    let (content, used) = der::decode_sequence(info).map_err(| _ | PadError::InvalidPadding)?;
+    let (_, alg_used) = der::decode_sequence(content).map_err(| _ | PadError::InvalidPadding)?;
+    let (digest, digest_used) = der::decode_octet_string(&content[alg_used..]).map_err(| _ | PadError::InvalidPadding)?;
+
+    if used != info.len() || alg_used + digest_used != content.len()
+    {
+        return Err(PadError::InvalidPadding);
+    }
+
+    Ok(digest)
+}
+
+/// Applique le padding de signature PKCS#1 v1.5 (EMSA-PKCS1-v1_5) à une structure DigestInfo : `0x00 0x01 FF...FF 0x00 T`.
+/// Au moins 8 octets `0xFF` sont requis ; `t` ne doit donc pas dépasser `k - 11` octets.
+pub fn pad_pkcs1_sign(t: &[u8], k: usize) -> Result<Vec<u8>, PadError>
+{
+    if t.len() + PKCS1_MIN_PS + 3 > k
+    {
+        return Err(PadError::MessageTooLong);
+    }
+
+    let mut em = vec![0x00, 0x01];
+    em.resize(k - t.len() - 1, 0xff);
+    em.push(0x00);
+    em.extend_from_slice(t);
+
+    Ok(em)
+}
+
+/// Retire le padding de signature PKCS#1 v1.5 d'un bloc (de la taille du module) et retourne la structure DigestInfo.
+pub fn unpad_pkcs1_sign(em: &[u8]) -> Result<Vec<u8>, PadError>
+{
+    if em.len() < PKCS1_MIN_PS + 3 || em[0] != 0x00 || em[1] != 0x01
+    {
+        return Err(PadError::InvalidPadding);
+    }
+
+    // Seuls des octets 0xFF sont autorisés avant le séparateur
+    match em[2..].iter().position(| &b | b != 0xff)
+    {
+        Some(ps_len) if ps_len >= PKCS1_MIN_PS && em[ps_len + 2] == 0x00 => Ok(em[(ps_len + 3)..].to_vec()),
+        _ => Err(PadError::InvalidPadding)
+    }
+}
+
+/// Compare deux suites d'octets en temps constant (pour une longueur donnée) : tous les octets sont examinés, quelle que soit la première différence.
+///
+/// Les différences sont accumulées par OU binaire sans branchement dépendant des données, et l'accumulateur est masqué à
+/// l'optimiseur (`black_box`) afin qu'il ne réintroduise pas de sortie anticipée. Seule la longueur n'est pas protégée :
+/// deux suites de longueurs différentes sont rejetées immédiatement, la longueur étant en général publique (taille d'empreinte, de module).
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool
+{
+    if a.len() != b.len()
+    {
+        return false;
+    }
+
+    let diff = a.iter().zip(b.iter()).fold(0u8, | diff, (x, y) | core::hint::black_box(diff | (x ^ y)));
+
+    diff == 0
+}
+
+
+/// Fonction de génération de masque MGF1 (RFC 8017, B.2.1) sur la fonction de hachage `hash` : produit `len` octets depuis `seed`.
+pub fn mgf1_with(hash: HashFn, seed: &[u8], len: usize) -> Vec<u8>
+{
+    let mut mask = Vec::with_capacity(len);
+    let mut counter = 0u32;
+
+    // On concatène H(seed || compteur) jusqu'à obtenir assez d'octets
+    while mask.len() < len
+    {
+        let mut block = seed.to_vec();
+        block.extend_from_slice(&counter.to_be_bytes());
+        mask.extend(hash(&block));
+        counter += 1;
+    }
+    mask.truncate(len);
+
+    mask
+}
+
+/// Fonction de génération de masque MGF1 sur SHA-256.
+pub fn mgf1(seed: &[u8], len: usize) -> Vec<u8>
+{
+    mgf1_with(hash::sha256, seed, len)
+}
+
+/// Applique le masque `mask` à `data` (ou exclusif octet par octet).
+fn xor_in_place(data: &mut [u8], mask: &[u8])
+{
+    for (d, m) in data.iter_mut().zip(mask.iter())
+    {
+        *d ^= m;
+    }
+}
+
+/// Taille maximale en octets d'un message chiffrable avec le padding OAEP, pour un module de `k` octets et une fonction de hachage
+/// dont l'empreinte fait `hash_len` octets (32 pour SHA-256) : `k - 2 * hash_len - 2`, ou `None` si le module est trop petit pour OAEP.
+/// Permet de découper un message avant son chiffrement plutôt que d'obtenir `PadError::MessageTooLong`.
+pub fn oaep_max_message_len(k: usize, hash_len: usize) -> Option<usize>
+{
+    k.checked_sub(2 * hash_len + 2)
+}
+
+/// Applique le padding OAEP (RFC 8017, EME-OAEP) à un message avec l'étiquette `label`, la fonction de hachage `hash` et MGF1.
+/// `k` est la taille en octets du module ; le message ne doit pas dépasser `oaep_max_message_len(k, hLen)` octets.
+pub fn pad_oaep_with(hash: HashFn, msg: &[u8], label: &[u8], k: usize, rng: &mut impl Rng) -> Result<Vec<u8>, PadError>
+{
+    let lhash = hash(label);
+    let hlen = lhash.len();
+
+    if oaep_max_message_len(k, hlen).is_none_or(| max | msg.len() > max)
+    {
+        return Err(PadError::MessageTooLong);
+    }
+
+    // DB = lHash || PS (zéros) || 0x01 || M
+    let mut db = lhash;
+    db.resize(k - msg.len() - hlen - 2, 0x00);
+    db.push(0x01);
+    db.extend_from_slice(msg);
+
+    let seed: Vec<u8> = (0..hlen).map(| _ | rng.gen()).collect();
+    let mut masked_seed = seed.clone();
+
+    xor_in_place(&mut db, &mgf1_with(hash, &seed, k - hlen - 1));
+    xor_in_place(&mut masked_seed, &mgf1_with(hash, &db, hlen));
+
+    // EM = 0x00 || maskedSeed || maskedDB
+    let mut em = Vec::with_capacity(k);
+    em.push(0x00);
+    em.extend(masked_seed);
+    em.extend(db);
+
+    Ok(em)
+}
+
+/// Retire le padding OAEP d'un bloc déchiffré (de la taille du module) et retourne le message.
+/// Toutes les vérifications sont effectuées avant de conclure, sans retour anticipé à la première incohérence.
+pub fn unpad_oaep_with(hash: HashFn, em: &[u8], label: &[u8]) -> Result<Vec<u8>, PadError>
+{
+    let lhash = hash(label);
+    let hlen = lhash.len();
+    let k = em.len();
+
+    // La taille ne dépend pas du contenu : on peut la vérifier immédiatement
+    if k < 2 * hlen + 2
+    {
+        return Err(PadError::InvalidPadding);
+    }
+
+    let mut seed = em[1..=hlen].to_vec();
+    let mut db = em[(hlen + 1)..].to_vec();
+    xor_in_place(&mut seed, &mgf1_with(hash, &db, hlen));
+    xor_in_place(&mut db, &mgf1_with(hash, &seed, k - hlen - 1));
+
+    // Premier octet nul et lHash identique
+    let mut diff = em[0];
+    for (a, b) in db[..hlen].iter().zip(lhash.iter())
+    {
+        diff |= a ^ b;
+    }
+    let mut valid = diff == 0;
+
+    // Recherche du séparateur 0x01 : seuls des zéros sont autorisés avant lui
+    let mut found = false;
+    let mut index = 0usize;
+    for (i, &b) in db[hlen..].iter().enumerate()
+    {
+        let is_sep = b == 0x01;
+        let first = is_sep & !found;
+        index |= usize::from(first).wrapping_neg() & i;
+        valid &= found | is_sep | (b == 0x00);
+        found |= is_sep;
+    }
+    valid &= found;
+
+    if !valid
+    {
+        return Err(PadError::InvalidPadding);
+    }
+
+    Ok(db[(hlen + index + 1)..].to_vec())
+}
+
+/// Applique le padding OAEP avec SHA-256 (voir `pad_oaep_with`).
+pub fn pad_oaep(msg: &[u8], label: &[u8], k: usize, rng: &mut impl Rng) -> Result<Vec<u8>, PadError>
+{
+    pad_oaep_with(hash::sha256, msg, label, k, rng)
+}
+
+/// Retire le padding OAEP avec SHA-256 (voir `unpad_oaep_with`).
+pub fn unpad_oaep(em: &[u8], label: &[u8]) -> Result<Vec<u8>, PadError>
+{
+    unpad_oaep_with(hash::sha256, em, label)
+}
+
+
+/// Calcule `H = Hash(0x00 * 8 || mHash || salt)`, l'empreinte intermédiaire d'EMSA-PSS.
+fn pss_hash(mhash: &[u8], salt: &[u8]) -> Vec<u8>
+{
+    let mut m = vec![0u8; 8];
+    m.extend_from_slice(mhash);
+    m.extend_from_slice(salt);
+
+    hash::sha256(&m)
+}
+
+/// Encode une empreinte SHA-256 selon EMSA-PSS (RFC 8017, 9.1.1) avec un sel aléatoire de `salt_len` octets et MGF1 sur SHA-256.
+/// `em_bits` est la taille maximale en bits de l'encodage (taille du module moins un) : les bits de poids fort excédentaires sont mis à zéro.
+pub fn emsa_pss_encode(mhash: &[u8], em_bits: usize, salt_len: usize, rng: &mut impl Rng) -> Result<Vec<u8>, PadError>
+{
+    let hlen = hash::SHA256_LEN;
+    let em_len = em_bits.div_ceil(8);
+    if mhash.len() != hlen || em_len < hlen + salt_len + 2
+    {
+        return Err(PadError::MessageTooLong);
+    }
+
+    let salt: Vec<u8> = (0..salt_len).map(| _ | rng.gen()).collect();
+    let h = pss_hash(mhash, &salt);
+
+    // DB = PS (zéros) || 0x01 || sel
+    let mut db = vec![0u8; em_len - salt_len - hlen - 2];
+    db.push(0x01);
+    db.extend(salt);
+    xor_in_place(&mut db, &mgf1(&h, em_len - hlen - 1));
+    db[0] &= 0xff >> (8 * em_len - em_bits);
+
+    // EM = maskedDB || H || 0xbc
+    let mut em = db;
+    em.extend(h);
+    em.push(PSS_TRAILER);
+
+    Ok(em)
+}
+
+/// Vérifie qu'un encodage EMSA-PSS (RFC 8017, 9.1.2) de `em_len = ceil(em_bits / 8)` octets correspond à l'empreinte `mhash`,
+/// avec un sel de `salt_len` octets. `H` est reconstruite à partir du sel extrait puis comparée en temps constant.
+pub fn emsa_pss_verify(mhash: &[u8], em: &[u8], em_bits: usize, salt_len: usize) -> bool
+{
+    let hlen = hash::SHA256_LEN;
+    let em_len = em_bits.div_ceil(8);
+    if mhash.len() != hlen || em.len() != em_len || em_len < hlen + salt_len + 2 || em[em_len - 1] != PSS_TRAILER
+    {
+        return false;
+    }
+
+    let (masked_db, h) = (&em[..(em_len - hlen - 1)], &em[(em_len - hlen - 1)..(em_len - 1)]);
+    let top_mask = 0xff >> (8 * em_len - em_bits);
+    if masked_db[0] & !top_mask != 0
+    {
+        return false;
+    }
+
+    let mut db = masked_db.to_vec();
+    xor_in_place(&mut db, &mgf1(h, em_len - hlen - 1));
+    db[0] &= top_mask;
+
+    // PS ne doit contenir que des zéros, suivis du séparateur 0x01
+    let ps_len = em_len - hlen - salt_len - 2;
+    if db[..ps_len].iter().any(| &b | b != 0x00) || db[ps_len] != 0x01
+    {
+        return false;
+    }
+
+    ct_eq(h, &pss_hash(mhash, &db[(ps_len + 1)..]))
+}
\ No newline at end of file
@@ -0,0 +1,1199 @@
+use crate::{der, hash::{self, DigestAlgorithm}, logging::{self, Level}, maths::{self, NumUtil}, messages, pkcs1, zeroize::Zeroize};
+use num_bigint::{BigUint, RandBigInt, ToBigInt};
+use num_traits::{One, Zero};
+use rand::{CryptoRng, Rng};
+use std::{error::Error, fmt::{Debug, Display}, hash::{Hash, Hasher}, string::FromUtf8Error, sync::atomic::{AtomicBool, Ordering}, time::Instant};
+
+
+/// Exposant public utilisé par défaut pour la génération des clés RSA autonomes.
+pub const PUB_EXP_DEF: u32 = 65537;
+/// Exposants publics essayés, dans cet ordre, lorsque l'exposant demandé n'est pas premier avec l'indicatrice des nombres premiers tirés
+/// (voir `choose_exponent`) : seuls ceux supérieurs à l'exposant demandé sont essayés, avant de tirer de nouveaux nombres premiers.
+const PUB_EXP_FALLBACK: [u32; 5] = [3, 5, 17, 257, 65537];
+/// Taille du module, en bits, des clés générées par défaut par `KeyGenBuilder`.
+pub const KEYGEN_BITS_DEF: u64 = 2048;
+/// Taille minimale du module, en bits, acceptée par défaut par `KeyPair::generate` et `KeyGenBuilder` (voir `KeyGenBuilder::min_bits`).
+pub const KEYGEN_MIN_BITS_DEF: u64 = 512;
+/// Taille de module, en bits, en dessous de laquelle une clé est considérée comme faible (voir `KeyPair::is_weak`) :
+/// de telles clés restent utilisables pour les tests, mais sont à proscrire en pratique.
+pub const KEY_BITS_RECOMMENDED: u64 = 2048;
+/// Nombre maximal de candidats premiers tirés par défaut lors de la recherche d'un nombre premier, par bit de module
+/// (voir `KeyGenBuilder::max_attempts`) : bien au-delà de ce qu'exige un générateur sain, il borne la recherche d'un générateur défaillant.
+pub const KEYGEN_ATTEMPTS_PER_BIT_DEF: u64 = maths::PRIME_ATTEMPTS_PER_BIT_DEF;
+/// Écart minimal, en bits, entre la taille des nombres premiers et celle de leur différence `|p - q|` lors de la génération.
+const PRIME_DISTANCE_MARGIN: u64 = 100;
+/// Octet marquant la fin du clair dans le remplissage du mode chaîné (`encrypt_cbc`), suivi de zéros jusqu'à la fin du bloc.
+const CBC_PAD_MARKER: u8 = 0x80;
+/// Nombre d'octets de l'empreinte SHA-256 conservés par `PublicKey::fingerprint`.
+const FINGERPRINT_LEN: usize = 16;
+
+
+/// Incohérences pouvant être détectées par `KeyPair::validate`, paramètres de génération invalides ou génération abandonnée (`KeyGenBuilder::build`).
+#[derive(Debug, PartialEq, Eq)]
+pub enum KeyError
+{
+    /// L'exposant public est pair, inférieur ou égal à 1, ou supérieur ou égal au module (`KeyPair::from_components`).
+    InvalidExponent,
+    /// Le module est inférieur à 256 : il ne laisse aucun octet aux blocs de clair (`block_size` nul), la clé ne peut pas chiffrer.
+    ModulusTooSmall,
+    /// L'exposant privé n'est pas strictement compris entre 1 et le module (`KeyPair::from_components`).
+    InvalidPrivateExponent,
+    /// Le module n'est pas le produit des facteurs premiers de la clé.
+    ModulusMismatch,
+    /// L'exposant public n'est pas premier avec l'indicatrice d'Euler du module.
+    ExponentNotCoprime,
+    /// L'exposant privé n'est pas l'inverse de l'exposant public modulo l'indicatrice de Carmichael du module.
+    InverseMismatch,
+    /// Moins de deux nombres premiers ont été demandés pour la génération (`KeyGenBuilder::primes`).
+    InvalidPrimeCount,
+    /// La taille de module demandée est inférieure au minimum autorisé (`KEYGEN_MIN_BITS_DEF` par défaut, voir `KeyGenBuilder::min_bits`).
+    KeySizeTooSmall,
+    /// Aucun nombre premier n'a été trouvé parmi le nombre maximal de candidats autorisé (voir `KeyGenBuilder::max_attempts`) :
+    /// le générateur aléatoire est probablement défaillant.
+    ExhaustedAttempts,
+    /// Les paramètres CRT (`dp`, `dq`, `qinv` ou ceux d'un facteur supplémentaire) ne correspondent pas à l'exposant privé et aux facteurs premiers.
+    CrtMismatch,
+    /// La génération a été interrompue par l'appelant (`KeyPair::generate_cancellable`).
+    Cancelled
+}
+
+impl Display for KeyError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            KeyError::InvalidExponent => write!(f, "Clé RSA invalide (KeyError) : exposant public pair ou hors de l'intervalle ]1, n["),
+            KeyError::InvalidPrivateExponent => write!(f, "Clé RSA invalide (KeyError) : exposant privé hors de l'intervalle ]1, n["),
+            KeyError::ModulusTooSmall => write!(f, "Clé RSA invalide (KeyError) : module inférieur à 256, aucun bloc de clair possible"),
+            KeyError::ModulusMismatch => write!(f, "Clé RSA invalide (KeyError) : le module n'est pas le produit des facteurs premiers"),
+            KeyError::ExponentNotCoprime => write!(f, "Clé RSA invalide (KeyError) : exposant public non premier avec l'indicatrice"),
+            KeyError::InverseMismatch => write!(f, "Clé RSA invalide (KeyError) : exposant privé incohérent avec l'exposant public"),
+            KeyError::InvalidPrimeCount => write!(f, "Clé RSA invalide (KeyError) : au moins deux nombres premiers sont nécessaires"),
+            KeyError::KeySizeTooSmall => write!(f, "Clé RSA invalide (KeyError) : taille de module inférieure au minimum autorisé"),
+            KeyError::ExhaustedAttempts => write!(f, "Génération de clé RSA abandonnée (KeyError) : nombre maximal de candidats premiers atteint"),
+            KeyError::CrtMismatch => write!(f, "Clé RSA invalide (KeyError) : paramètres CRT incohérents avec l'exposant privé"),
+            KeyError::Cancelled => write!(f, "Génération de clé RSA abandonnée (KeyError) : interrompue par l'appelant")
+        }
+    }
+}
+
+impl Error for KeyError {}
+
+
+/// Étapes de la génération d'une clé signalées à l'appelant de `KeyPair::generate_with_progress`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeygenEvent
+{
+    /// Un candidat premier a été tiré et testé (qu'il soit premier ou non).
+    CandidateTested,
+    /// Le premier nombre premier (p) a été trouvé.
+    FirstPrimeFound,
+    /// Le second nombre premier (q) a été trouvé. Si l'exposant public n'est pas premier avec l'indicatrice,
+    /// la génération recommence et les événements sont émis à nouveau.
+    SecondPrimeFound
+}
+
+
+/// Paramètres du théorème des restes chinois (CRT) d'une clé RSA, permettant d'accélérer les opérations privées.
+pub struct CrtParams
+{
+    /// Premier facteur premier du module.
+    pub p: BigUint,
+    /// Second facteur premier du module.
+    pub q: BigUint,
+    /// `d mod (p - 1)`
+    pub dp: BigUint,
+    /// `d mod (q - 1)`
+    pub dq: BigUint,
+    /// `q^-1 mod p`
+    pub qinv: BigUint,
+    /// Facteurs premiers supplémentaires d'une clé multi-premiers (vide pour une clé à deux nombres premiers).
+    pub others: Vec<OtherPrime>
+}
+
+/// Facteur premier supplémentaire d'une clé RSA multi-premiers (`OtherPrimeInfo` de PKCS#1), avec son exposant CRT.
+pub struct OtherPrime
+{
+    /// Facteur premier `r_i` du module.
+    pub r: BigUint,
+    /// `d mod (r_i - 1)`
+    pub d: BigUint,
+    /// Coefficient CRT `(p * q * r_1 * ... * r_(i-1))^-1 mod r_i`
+    pub t: BigUint
+}
+
+
+/// Clé RSA autonome, contenant directement le module et les deux exposants.
+/// Contrairement à `engines::RsaKey` (paire de paires de clés numériques utilisée par le moteur), cette structure regroupe toutes les valeurs RSA en un seul endroit.
+pub struct KeyPair
+{
+    /// Module RSA, produit des deux nombres premiers p et q (et des éventuels facteurs supplémentaires d'une clé multi-premiers).
+    pub n: BigUint,
+    /// Exposant public.
+    pub e: BigUint,
+    /// Exposant privé, inverse de `e` modulo l'indicatrice d'Euler de `n`.
+    pub d: BigUint,
+    /// Paramètres CRT, présents si les facteurs premiers du module sont connus (c'est le cas après une génération).
+    pub crt: Option<CrtParams>
+}
+
+/// Les valeurs secrètes sont effacées (voir `zeroize::Zeroize`) avant la libération de la clé.
+impl Drop for KeyPair
+{
+    fn drop(&mut self)
+    {
+        self.d.zeroize();
+    }
+}
+
+impl Drop for CrtParams
+{
+    fn drop(&mut self)
+    {
+        for secret in [&mut self.p, &mut self.q, &mut self.dp, &mut self.dq, &mut self.qinv]
+        {
+            secret.zeroize();
+        }
+    }
+}
+
+/// Les valeurs secrètes ne sont pas affichées : seules la taille du module et l'exposant public apparaissent,
+/// afin qu'une clé journalisée par mégarde ne divulgue pas l'exposant privé.
+impl Debug for KeyPair
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_struct("KeyPair")
+            .field("n_bits", &self.n.bits())
+            .field("e", &self.e)
+            .field("d", &"[redacted]")
+            .field("crt", &self.crt.as_ref().map(| _ | "[redacted]"))
+            .finish()
+    }
+}
+
+/// Deux clés sont égales si elles ont le même module et les mêmes exposants ; les paramètres CRT, qui s'en déduisent, ne sont pas comparés.
+/// Les exposants privés sont comparés en temps constant sur la taille du module.
+impl PartialEq for KeyPair
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        let k = self.n.sz_b() as usize;
+
+        self.n == other.n && self.e == other.e
+            && pkcs1::ct_eq(&messages::biguint_to_bytes(&self.d, k), &messages::biguint_to_bytes(&other.d, k))
+    }
+}
+
+impl Eq for KeyPair {}
+
+/// Seule la partie publique (`n`, `e`) est hachée, comme pour `PublicKey` : l'exposant privé n'influence pas le haché,
+/// qui reste cohérent avec l'égalité et avec l'empreinte (`PublicKey::fingerprint`).
+impl Hash for KeyPair
+{
+    fn hash<H: Hasher>(&self, state: &mut H)
+    {
+        self.n.hash(state);
+        self.e.hash(state);
+    }
+}
+
+impl Drop for OtherPrime
+{
+    fn drop(&mut self)
+    {
+        for secret in [&mut self.r, &mut self.d, &mut self.t]
+        {
+            secret.zeroize();
+        }
+    }
+}
+
+impl KeyPair
+{
+    /// Génère une clé RSA dont le module fait environ `bits` bits, les nombres premiers étant tirés depuis l'entropie du système (`OsRng`).
+    /// Retourne `KeyError::KeySizeTooSmall` en dessous de `KEYGEN_MIN_BITS_DEF` bits (voir `KeyGenBuilder::allow_small` pour générer
+    /// une clé plus petite) ; une clé de moins de `KEY_BITS_RECOMMENDED` bits est acceptée mais faible, et
+    /// `KeyError::ExhaustedAttempts` si la recherche d'un nombre premier dépasse `KEYGEN_ATTEMPTS_PER_BIT_DEF * bits` candidats. Voir `generate_with`.
+    ///
+    /// La génération d'une clé faible n'est signalée (avertissement `logging::Level::Warn`) qu'avec la fonctionnalité `logging` :
+    /// sans elle, rien ne la distingue d'une clé sûre, et il revient à l'appelant de vérifier `is_weak` sur la clé obtenue.
+    /// Il en va de même pour les autres générateurs (`generate_with`, `generate_multiprime`...) et pour `KeyGenBuilder::build`.
+    pub fn generate(bits: u64) -> Result<KeyPair, KeyError>
+    {
+        check_key_size(bits)?;
+
+        let e = BigUint::from(PUB_EXP_DEF);
+        Self::generate_capped(&mut rand::rngs::OsRng, bits, &e, maths::PRIME_ROUNDS_DEF, default_max_attempts(bits))
+    }
+
+    /// Voir `generate`, en signalant l'avancement de la génération à `progress` (pour afficher une progression par exemple).
+    pub fn generate_with_progress(bits: u64, progress: &mut dyn FnMut(KeygenEvent)) -> Result<KeyPair, KeyError>
+    {
+        check_key_size(bits)?;
+
+        let rng = &mut rand::rngs::OsRng;
+        let (prime_bits, max_attempts) = (bits / 16 * 8, default_max_attempts(bits));
+        let sieve = maths::small_prime_sieve(maths::SIEVE_LIMIT_DEF);
+
+        Self::generate_from_primes(prime_bits, | progress |
+        {
+            rand_prime(rng, prime_bits, &sieve, maths::PRIME_ROUNDS_DEF, max_attempts, progress)
+        }, &BigUint::from(PUB_EXP_DEF), progress).ok_or(KeyError::ExhaustedAttempts)
+    }
+
+    /// Voir `generate`, la génération pouvant être interrompue depuis un autre thread (sur Ctrl-C par exemple) en levant le drapeau `cancel`.
+    /// Le drapeau est consulté avant chaque candidat premier ; retourne `KeyError::Cancelled` si la génération a été interrompue.
+    pub fn generate_cancellable(bits: u64, cancel: &AtomicBool) -> Result<KeyPair, KeyError>
+    {
+        check_key_size(bits)?;
+
+        let rng = &mut rand::rngs::OsRng;
+        let (prime_bits, max_attempts) = (bits / 16 * 8, default_max_attempts(bits));
+        let sieve = maths::small_prime_sieve(maths::SIEVE_LIMIT_DEF);
+
+        Self::generate_from_primes(prime_bits, | progress |
+        {
+            rand_prime_cancellable(rng, prime_bits, &sieve, maths::PRIME_ROUNDS_DEF, max_attempts, progress, cancel)
+        }, &BigUint::from(PUB_EXP_DEF), &mut | _ | {})
+            .ok_or_else(|| if cancel.load(Ordering::Relaxed) { KeyError::Cancelled } else { KeyError::ExhaustedAttempts })
+    }
+
+    /// Génère une clé RSA dont le module fait environ `bits` bits : chacun des deux nombres premiers en fait environ la moitié.
+    /// Le générateur doit être cryptographiquement sûr (`CryptoRng`). `rounds` est le nombre d'itérations du test de Miller-Rabin
+    /// (20 si `None`). Voir `generate_from_primes` pour les conditions imposées aux nombres premiers, et `generate` pour les erreurs
+    /// (taille minimale, nombre maximal de candidats).
+    pub fn generate_with<R: Rng + CryptoRng>(rng: &mut R, bits: u64, rounds: Option<u32>) -> Result<KeyPair, KeyError>
+    {
+        check_key_size(bits)?;
+
+        let rounds = rounds.unwrap_or(maths::PRIME_ROUNDS_DEF);
+        Self::generate_capped(rng, bits, &BigUint::from(PUB_EXP_DEF), rounds, default_max_attempts(bits))
+    }
+
+    /// Voir `generate_with`, avec l'exposant public `e`, la génération étant abandonnée (`KeyError::ExhaustedAttempts`)
+    /// dès que la recherche d'un nombre premier dépasse `max_attempts` candidats.
+    fn generate_capped<R>(rng: &mut R, bits: u64, e: &BigUint, rounds: u32, max_attempts: u64) -> Result<KeyPair, KeyError>
+    where R: Rng + CryptoRng
+    {
+        let prime_bits = bits / 16 * 8;
+        let sieve = maths::small_prime_sieve(maths::SIEVE_LIMIT_DEF);
+
+        Self::generate_from_primes(prime_bits, | progress | rand_prime(rng, prime_bits, &sieve, rounds, max_attempts, progress), e, &mut | _ | {})
+            .ok_or(KeyError::ExhaustedAttempts)
+    }
+
+    /// Génère une clé RSA dont le module fait environ `bits` bits en testant les candidats premiers sur `threads` threads en parallèle
+    /// (au moins un). Chaque thread dispose de son propre générateur `StdRng`, initialisé depuis `OsRng` ; le premier nombre premier trouvé
+    /// est retenu et les autres threads s'arrêtent. Voir `generate_with`, y compris pour le nombre maximal de candidats (tous threads confondus).
+    #[cfg(feature = "parallel")]
+    pub fn generate_parallel(bits: u64, threads: usize) -> Result<KeyPair, KeyError>
+    {
+        check_key_size(bits)?;
+
+        let (prime_bits, max_attempts) = (bits / 16 * 8, default_max_attempts(bits));
+        let sieve = maths::small_prime_sieve(maths::SIEVE_LIMIT_DEF);
+
+        Self::generate_from_primes(prime_bits, | _ |
+        {
+            rand_prime_parallel(prime_bits, &sieve, maths::PRIME_ROUNDS_DEF, threads.max(1), max_attempts)
+        }, &BigUint::from(PUB_EXP_DEF), &mut | _ | {}).ok_or(KeyError::ExhaustedAttempts)
+    }
+
+    /// Construit une clé RSA depuis les nombres premiers de `prime_bits` bits fournis par `draw`, qui reçoit `progress` pour y signaler
+    /// ses candidats ; l'obtention de p et de q y est signalée ensuite.
+    /// q est tiré à nouveau tant que `|p - q|` est inférieur à `2^(prime_bits - PRIME_DISTANCE_MARGIN)` (p et q trop proches
+    /// rendent le module vulnérable à la factorisation de Fermat, voir `maths::fermat_factor`), et la génération recommence
+    /// tant que ni l'exposant public `e` ni ses remplaçants (voir `choose_exponent`) ne sont premiers avec l'indicatrice.
+    /// La génération est abandonnée (`None`) dès que `draw` ne fournit pas de nombre premier.
+    pub(crate) fn generate_from_primes<F>(prime_bits: u64, mut draw: F, e: &BigUint, progress: &mut dyn FnMut(KeygenEvent)) -> Option<KeyPair>
+    where F: FnMut(&mut dyn FnMut(KeygenEvent)) -> Option<BigUint>
+    {
+        loop
+        {
+            let p = draw(progress)?;
+            progress(KeygenEvent::FirstPrimeFound);
+            let mut q = draw(progress)?;
+            while primes_too_close(&p, &q, prime_bits)
+            {
+                q = draw(progress)?;
+            }
+            progress(KeygenEvent::SecondPrimeFound);
+
+            let phi = (&p - 1u8) * (&q - 1u8);
+            let e = match choose_exponent(e, &phi)
+            {
+                Some(e) => e,
+                None => continue
+            };
+
+            // L'inverse existe forcément puisque e et phi sont premiers entre eux
+            let d = inverse(&e, &phi).unwrap();
+            let crt = CrtParams
+            {
+                dp: &d % (&p - 1u8),
+                dq: &d % (&q - 1u8),
+                qinv: inverse(&q, &p).unwrap(),
+                p,
+                q,
+                others: Vec::new()
+            };
+
+            return Some(KeyPair
+            {
+                n: &crt.p * &crt.q,
+                e,
+                d,
+                crt: Some(crt)
+            });
+        }
+    }
+
+    /// Génère une clé RSA multi-premiers (PKCS#1) dont le module est le produit de `nprimes` nombres premiers distincts, tirés depuis `OsRng`.
+    /// Les `bits` bits sont répartis entre les nombres premiers, et la génération recommence tant que deux d'entre eux sont trop proches
+    /// (voir `generate_from_primes`) ou que le module ne fait pas exactement `bits` bits.
+    /// Retourne `KeyError::InvalidPrimeCount` si `nprimes` est inférieur à 2, et les erreurs de `generate` sinon (taille minimale,
+    /// nombre maximal de candidats).
+    pub fn generate_multiprime(bits: u64, nprimes: usize) -> Result<KeyPair, KeyError>
+    {
+        check_key_size(bits)?;
+        if nprimes < 2
+        {
+            return Err(KeyError::InvalidPrimeCount);
+        }
+
+        Self::generate_multiprime_with(&mut rand::rngs::OsRng, bits, nprimes, &BigUint::from(PUB_EXP_DEF), maths::PRIME_ROUNDS_DEF, default_max_attempts(bits))
+    }
+
+    /// Voir `generate_multiprime`, avec le générateur `rng`, l'exposant public `e` et `rounds` itérations du test de Miller-Rabin.
+    /// Retourne `KeyError::ExhaustedAttempts` dès que la recherche d'un nombre premier dépasse `max_attempts` candidats.
+    fn generate_multiprime_with<R>(rng: &mut R, bits: u64, nprimes: usize, e: &BigUint, rounds: u32, max_attempts: u64) -> Result<KeyPair, KeyError>
+    where R: Rng + CryptoRng
+    {
+        let sieve = maths::small_prime_sieve(maths::SIEVE_LIMIT_DEF);
+        // Les premiers nombres premiers reçoivent un bit de plus si `bits` n'est pas un multiple de `nprimes`
+        let k = nprimes as u64;
+        let sizes: Vec<u64> = (0..k).map(| i | bits / k + u64::from(i < bits % k)).collect();
+
+        loop
+        {
+            let primes: Vec<BigUint> = sizes.iter().map(| &size | rand_prime(rng, size, &sieve, rounds, max_attempts, &mut | _ | {}))
+                .collect::<Option<_>>().ok_or(KeyError::ExhaustedAttempts)?;
+            if primes.iter().enumerate().any(| (i, p) | primes[..i].iter().any(| q | primes_too_close(p, q, sizes[i])))
+            {
+                continue;
+            }
+
+            let n = primes.iter().fold(BigUint::one(), | acc, p | acc * p);
+            if n.bits() != bits
+            {
+                continue;
+            }
+            let phi = primes.iter().fold(BigUint::one(), | acc, p | acc * (p - 1u8));
+            let e = match choose_exponent(e, &phi)
+            {
+                Some(e) => e,
+                None => continue
+            };
+
+            let d = inverse(&e, &phi).unwrap();
+            let (p, q) = (&primes[0], &primes[1]);
+            // Le coefficient de chaque facteur supplémentaire est l'inverse du produit des facteurs qui le précèdent
+            let mut prefix = p * q;
+            let others = primes[2..].iter().map(| r | {
+                let other = OtherPrime { r: r.clone(), d: &d % (r - 1u8), t: inverse(&prefix, r).unwrap() };
+                prefix *= r;
+                other
+            }).collect();
+            let crt = CrtParams
+            {
+                dp: &d % (p - 1u8),
+                dq: &d % (q - 1u8),
+                qinv: inverse(q, p).unwrap(),
+                p: p.clone(),
+                q: q.clone(),
+                others
+            };
+
+            return Ok(KeyPair { n, e, d, crt: Some(crt) });
+        }
+    }
+
+    /// Construit une clé sans paramètres CRT à partir de composantes stockées, après vérification de `n >= 256` (`KeyError::ModulusTooSmall`),
+    /// de `1 < e < n` avec `e` impair et de `1 < d < n`. C'est le point d'entrée à privilégier pour reconstruire une clé désérialisée ; la relation entre `e` et `d`
+    /// n'est pas vérifiée ici (voir `validate`).
+    pub fn from_components(n: BigUint, e: BigUint, d: BigUint) -> Result<Self, KeyError>
+    {
+        check_modulus(&n)?;
+        check_exponent(&e, &n)?;
+        let one = BigUint::one();
+        if d <= one || d >= n
+        {
+            return Err(KeyError::InvalidPrivateExponent);
+        }
+
+        Ok(KeyPair { n, e, d, crt: None })
+    }
+
+    /// Construit une clé comme `from_components`, en dérivant les paramètres CRT des nombres premiers `p` et `q`,
+    /// puis vérifie l'ensemble de la clé avec `validate` (module égal à `p * q`, exposant privé inverse de l'exposant public).
+    pub fn from_components_crt(n: BigUint, e: BigUint, d: BigUint, p: BigUint, q: BigUint) -> Result<Self, KeyError>
+    {
+        let mut key = Self::from_components(n, e, d)?;
+        // Un facteur nul ou égal à 1 n'est pas premier (et rendrait `p - 1` ou `q - 1` nul ci-dessous)
+        let one = BigUint::one();
+        if p <= one || q <= one
+        {
+            return Err(KeyError::ModulusMismatch);
+        }
+        // q n'est pas inversible modulo p si les deux facteurs ne sont pas premiers entre eux (p = q, par exemple)
+        let qinv = inverse(&q, &p).ok_or(KeyError::ModulusMismatch)?;
+
+        key.crt = Some(CrtParams
+        {
+            dp: &key.d % (&p - 1u8),
+            dq: &key.d % (&q - 1u8),
+            qinv,
+            p,
+            q,
+            others: Vec::new()
+        });
+        key.validate()?;
+
+        Ok(key)
+    }
+
+    /// Construit une clé déterministe à partir de nombres premiers choisis, pour des tests rapides et reproductibles
+    /// (par exemple la clé du manuel `p = 61`, `q = 53`, `e = 17`, qui donne `n = 3233` et `d = 2753`).
+    /// L'exposant privé est l'inverse de `e` modulo l'indicatrice d'Euler `(p - 1)(q - 1)` ; la primalité de `p` et `q` n'est pas vérifiée.
+    /// Réservé aux tests (ou à la fonctionnalité `test-keys`).
+    #[cfg(any(test, feature = "test-keys"))]
+    pub fn from_known_primes(p: BigUint, q: BigUint, e: BigUint) -> Result<Self, KeyError>
+    {
+        if !e.bit(0) || e <= BigUint::one()
+        {
+            return Err(KeyError::InvalidExponent);
+        }
+        if p <= BigUint::one() || q <= BigUint::one()
+        {
+            return Err(KeyError::ModulusMismatch);
+        }
+
+        let d = inverse(&e, &((&p - 1u8) * (&q - 1u8))).ok_or(KeyError::ExponentNotCoprime)?;
+
+        Self::from_components_crt(&p * &q, e, d, p, q)
+    }
+
+    /// Vérifie la cohérence de la clé (par exemple après un import PEM ou JSON) : `n >= 256`, `e` impair et supérieur à 1, puis, si les facteurs
+    /// premiers sont connus, `n` égal à leur produit, `pgcd(e, phi(n)) == 1`, `e * d ≡ 1 mod lambda(n)` et la cohérence des paramètres CRT
+    /// (`dp = d mod (p - 1)`, `dq = d mod (q - 1)`, `qinv * q ≡ 1 mod p`, et de même pour chaque facteur supplémentaire).
+    /// Sans facteurs premiers, seule la relation `(2^e)^d ≡ 2 mod n` est vérifiée pour l'exposant privé.
+    pub fn validate(&self) -> Result<(), KeyError>
+    {
+        check_modulus(&self.n)?;
+        if !self.e.bit(0) || self.e <= BigUint::one()
+        {
+            return Err(KeyError::InvalidExponent);
+        }
+
+        let crt = match &self.crt
+        {
+            Some(crt) => crt,
+            None => {
+                let two = BigUint::from(2u8);
+                let m = &two % &self.n;
+                return if maths::fmodpow(&maths::fmodpow(&m, &self.e, &self.n), &self.d, &self.n) == m { Ok(()) } else { Err(KeyError::InverseMismatch) };
+            }
+        };
+
+        let mut primes = vec![&crt.p, &crt.q];
+        primes.extend(crt.others.iter().map(| other | &other.r));
+        if primes.iter().any(| &p | p <= &BigUint::one()) || primes.iter().fold(BigUint::one(), | acc, &p | acc * p) != self.n
+        {
+            return Err(KeyError::ModulusMismatch);
+        }
+
+        let factors: Vec<(BigUint, u32)> = primes.iter().map(| &p | (p.clone(), 1)).collect();
+        if !maths::gcd(&self.e, &maths::phi_from_primes(&factors)).is_one()
+        {
+            return Err(KeyError::ExponentNotCoprime);
+        }
+
+        if !((&self.e * &self.d) % maths::lambda_from_primes(&factors)).is_one()
+        {
+            return Err(KeyError::InverseMismatch);
+        }
+
+        if crt.dp != &self.d % (&crt.p - 1u8) || crt.dq != &self.d % (&crt.q - 1u8) || !((&crt.qinv * &crt.q) % &crt.p).is_one()
+        {
+            return Err(KeyError::CrtMismatch);
+        }
+        // Le coefficient de chaque facteur supplémentaire est l'inverse du produit des facteurs qui le précèdent
+        let mut prefix = &crt.p * &crt.q;
+        for other in crt.others.iter()
+        {
+            if other.d != &self.d % (&other.r - 1u8) || !((&other.t * &prefix) % &other.r).is_one()
+            {
+                return Err(KeyError::CrtMismatch);
+            }
+            prefix *= &other.r;
+        }
+
+        Ok(())
+    }
+
+    /// Déchiffre un seul nombre avec l'exposant privé. Si les paramètres CRT sont présents, le calcul se fait modulo p et q séparément
+    /// (environ quatre fois plus rapide) avant d'être recombiné ; sinon, l'exponentiation directe modulo `n` est utilisée.
+    /// Pour une clé multi-premiers, le calcul se fait modulo chaque facteur et les résultats sont recombinés par `maths::crt`.
+    pub fn decrypt_crt(&self, c: &BigUint) -> BigUint
+    {
+        logging::emit(Level::Debug, || String::from(match &self.crt
+        {
+            Some(crt) if !crt.others.is_empty() => "déchiffrement par CRT multi-premiers",
+            Some(_) => "déchiffrement par CRT",
+            None => "déchiffrement direct (exposant privé)"
+        }));
+
+        match &self.crt
+        {
+            Some(crt) if !crt.others.is_empty() => {
+                let mut residues = vec![maths::fmodpow(c, &crt.dp, &crt.p), maths::fmodpow(c, &crt.dq, &crt.q)];
+                let mut moduli = vec![crt.p.clone(), crt.q.clone()];
+                for other in crt.others.iter()
+                {
+                    residues.push(maths::fmodpow(c, &other.d, &other.r));
+                    moduli.push(other.r.clone());
+                }
+
+                // Les facteurs étant des nombres premiers distincts, la solution existe toujours
+                maths::crt(&residues, &moduli).expect("KeyPair.decrypt_crt")
+            },
+            Some(crt) => {
+                let m1 = maths::fmodpow(c, &crt.dp, &crt.p);
+                let m2 = maths::fmodpow(c, &crt.dq, &crt.q);
+                // m1 - m2 pouvant être négatif, la soustraction se fait modulo p : t = m1 + p - (m2 mod p) est dans ]0, 2p[,
+                // et p lui est retranché par sélection plutôt que par un branchement dépendant de m1 et m2
+                let t = m1 + &crt.p - (&m2 % &crt.p);
+                let t = &t - ct_select(t >= crt.p, &crt.p, &BigUint::zero());
+                let h = (t * &crt.qinv) % &crt.p;
+
+                m2 + &crt.q * h
+            },
+            None => maths::fmodpow(c, &self.d, &self.n)
+        }
+    }
+
+    /// Déchiffre un seul nombre comme `decrypt_crt`, avec aveuglement (blinding) contre les attaques par canaux auxiliaires :
+    /// un aléa `r` premier avec `n` est tiré depuis `rng`, `c * r^e mod n` est déchiffré, puis le résultat est multiplié par `r^-1 mod n`.
+    /// Le temps de calcul ne dépend ainsi plus directement de `c`. Si les facteurs premiers sont connus, `r^-1` est calculé par
+    /// `maths::mod_inverse_prime_power` plutôt que par l'algorithme d'Euclide étendu, dont les branchements dépendent de `r`.
+    pub fn decrypt_blinded(&self, c: &BigUint, rng: &mut impl Rng) -> BigUint
+    {
+        let two = BigUint::from(2u8);
+        let phi = self.crt.as_ref().map(| crt | {
+            let mut factors = vec![(crt.p.clone(), 1), (crt.q.clone(), 1)];
+            factors.extend(crt.others.iter().map(| other | (other.r.clone(), 1)));
+            maths::phi_from_primes(&factors)
+        });
+
+        loop
+        {
+            let r = rng.gen_biguint_range(&two, &self.n);
+            // r n'est pas inversible s'il partage un facteur avec n (cas négligeable, sauf pour un module minuscule)
+            let r_inv = match &phi
+            {
+                Some(phi) => {
+                    let r_inv = maths::mod_inverse_prime_power(&r, &self.n, phi);
+                    if !((&r * &r_inv) % &self.n).is_one()
+                    {
+                        continue;
+                    }
+                    r_inv
+                },
+                None => match inverse(&r, &self.n)
+                {
+                    Some(r_inv) => r_inv,
+                    None => continue
+                }
+            };
+
+            let blinded = (c * maths::fmodpow(&r, &self.e, &self.n)) % &self.n;
+            return (self.decrypt_crt(&blinded) * r_inv) % &self.n;
+        }
+    }
+
+    /// Taille du module en bits, qui donne la force de la clé.
+    pub fn bits(&self) -> u64
+    {
+        self.n.bits()
+    }
+
+    /// Indique si la clé est faible : module de moins de `KEY_BITS_RECOMMENDED` bits.
+    pub fn is_weak(&self) -> bool
+    {
+        self.bits() < KEY_BITS_RECOMMENDED
+    }
+
+    /// Taille en octets des blocs de clair : strictement inférieure à celle du module afin que chaque bloc soit plus petit que `n`.
+    pub fn block_size(&self) -> u32
+    {
+        self.n.sz_b() - 1
+    }
+
+    /// Chiffre un nombre de taille quelconque avec la clé publique (voir `PublicKey::encrypt`).
+    pub fn encrypt(&self, data: &BigUint) -> Vec<BigUint>
+    {
+        self.public_key().encrypt(data)
+    }
+
+    /// Chiffre un nombre comme `encrypt`, mais paresseusement : le nombre est découpé en blocs dès l'appel, et chaque bloc n'est chiffré
+    /// qu'à sa lecture depuis l'itérateur. Les blocs chiffrés peuvent ainsi être transmis au fur et à mesure, sans être tous conservés.
+    pub fn encrypt_iter<'a>(&'a self, data: &'a BigUint) -> impl Iterator<Item = BigUint> + 'a
+    {
+        data.expl_r(self.block_size()).into_iter().map(move | block | {
+            maths::fmodpow(&block, &self.e, &self.n)
+        })
+    }
+
+    /// Retourne la partie publique de la clé, qui ne contient aucune valeur secrète.
+    pub fn public_key(&self) -> PublicKey
+    {
+        PublicKey { n: self.n.clone(), e: self.e.clone() }
+    }
+
+    /// Déchiffre des blocs produits par `encrypt` et recompose le nombre original.
+    pub fn decrypt(&self, blocks: &[BigUint]) -> BigUint
+    {
+        // Chaque bloc déchiffré occupe exactement `block_size` octets, même s'il commence par des octets nuls
+        let shift = u64::from(self.block_size()) * 8;
+
+        blocks.iter().fold(BigUint::zero(), | acc, block | {
+            (acc << shift) + self.decrypt_crt(block)
+        })
+    }
+
+    /// Chiffre une chaîne de caractères : ses octets UTF-8 sont convertis en grand entier (`messages::bytes_to_biguint`),
+    /// puis chiffrés bloc par bloc comme par `encrypt`. Les caractères nuls (`'\0'`) de tête ne survivent pas à la conversion.
+    pub fn encrypt_str(&self, s: &str) -> Vec<BigUint>
+    {
+        self.encrypt(&messages::bytes_to_biguint(s.as_bytes()))
+    }
+
+    /// Déchiffre des blocs produits par `encrypt_str` et retrouve la chaîne originale. Retourne une erreur si les octets déchiffrés
+    /// ne sont pas de l'UTF-8 valide, ce qui est en pratique le cas d'un déchiffrement avec une autre clé.
+    pub fn decrypt_str(&self, blocks: &[BigUint]) -> Result<String, FromUtf8Error>
+    {
+        let m = self.decrypt(blocks);
+        // Zéro correspond à la chaîne vide, et non à un octet nul
+        let bytes = if m.is_zero() { Vec::new() } else { m.to_bytes_be() };
+
+        String::from_utf8(bytes)
+    }
+
+    /// Chiffre des octets en mode chaîné (CBC) : chaque bloc de clair de `block_size` octets est combiné par XOR avec les derniers octets
+    /// du bloc chiffré précédent avant d'être chiffré, afin que deux blocs de clair identiques donnent des blocs chiffrés différents.
+    /// Le vecteur d'initialisation aléatoire est écrit en premier bloc. Le clair est complété par `0x80` puis des zéros (ISO/IEC 7816-4).
+    /// Chaque bloc de sortie fait exactement `n.sz_b()` octets.
+    pub fn encrypt_cbc(&self, data: &[u8], rng: &mut impl Rng) -> Vec<u8>
+    {
+        let (b, k) = (self.block_size() as usize, self.n.sz_b() as usize);
+        let mut padded = data.to_vec();
+        padded.push(CBC_PAD_MARKER);
+        padded.resize(padded.len().div_ceil(b) * b, 0x00);
+
+        let mut prev = vec![0u8; b];
+        rng.fill_bytes(&mut prev);
+        let mut out = messages::biguint_to_bytes(&messages::bytes_to_biguint(&prev), k);
+
+        for block in padded.chunks(b)
+        {
+            let x: Vec<u8> = block.iter().zip(prev.iter()).map(| (m, p) | m ^ p).collect();
+            let c = messages::biguint_to_bytes(&maths::fmodpow(&messages::bytes_to_biguint(&x), &self.e, &self.n), k);
+            prev.copy_from_slice(&c[(k - b)..]);
+            out.extend(c);
+        }
+
+        out
+    }
+
+    /// Déchiffre des octets produits par `encrypt_cbc` et retire le remplissage. Retourne `PadError::InvalidPadding`
+    /// si la longueur n'est pas un multiple de `n.sz_b()`, si un bloc est invalide ou si le remplissage est absent.
+    pub fn decrypt_cbc(&self, data: &[u8]) -> Result<Vec<u8>, pkcs1::PadError>
+    {
+        let (b, k) = (self.block_size() as usize, self.n.sz_b() as usize);
+        if data.len() < 2 * k || !data.len().is_multiple_of(k) || data[..(k - b)].iter().any(| &x | x != 0x00)
+        {
+            return Err(pkcs1::PadError::InvalidPadding);
+        }
+
+        let mut prev = &data[(k - b)..k];
+        let mut out = Vec::with_capacity(data.len());
+        for block in data[k..].chunks(k)
+        {
+            let c = messages::bytes_to_biguint(block);
+            if c >= self.n
+            {
+                return Err(pkcs1::PadError::InvalidPadding);
+            }
+            let x = messages::biguint_to_bytes(&self.decrypt_crt(&c), b);
+            if x.len() != b
+            {
+                return Err(pkcs1::PadError::InvalidPadding);
+            }
+            out.extend(x.iter().zip(prev.iter()).map(| (x, p) | x ^ p));
+            prev = &block[(k - b)..];
+        }
+
+        // Le remplissage se termine au dernier octet non nul, qui doit être le marqueur
+        match out.iter().rposition(| &x | x != 0x00)
+        {
+            Some(end) if out[end] == CBC_PAD_MARKER => {
+                out.truncate(end);
+                Ok(out)
+            },
+            _ => Err(pkcs1::PadError::InvalidPadding)
+        }
+    }
+
+    /// Signe un message selon RSASSA-PKCS1-v1_5 : son empreinte par `alg` est placée dans la structure DigestInfo (avec l'OID de `alg`),
+    /// qui est paddée puis élevée à la puissance `d`. La signature retournée fait exactement la taille en octets du module.
+    /// Panique si le module est trop petit pour contenir l'empreinte paddée.
+    pub fn sign(&self, msg: &[u8], alg: &dyn DigestAlgorithm) -> Vec<u8>
+    {
+        let k = self.n.sz_b() as usize;
+        let em = pkcs1::pad_pkcs1_sign(&pkcs1::digest_info(&alg.digest(msg), alg.oid()), k).expect("KeyPair.sign");
+        let s = maths::fmodpow(&BigUint::from_bytes_be(&em), &self.d, &self.n);
+
+        messages::biguint_to_bytes(&s, k)
+    }
+
+    /// Signe une empreinte SHA-256 selon RSASSA-PSS (encodage EMSA-PSS avec MGF1 et un sel aléatoire de `salt_len` octets).
+    /// La signature étant probabiliste, deux signatures d'une même empreinte diffèrent.
+    /// Panique si le module est trop petit pour l'empreinte et le sel demandés.
+    pub fn sign_pss(&self, mhash: &[u8], salt_len: usize, rng: &mut impl Rng) -> Vec<u8>
+    {
+        let em_bits = self.n.bits() as usize - 1;
+        let em = pkcs1::emsa_pss_encode(mhash, em_bits, salt_len, rng).expect("KeyPair.sign_pss");
+        let s = maths::fmodpow(&BigUint::from_bytes_be(&em), &self.d, &self.n);
+
+        messages::biguint_to_bytes(&s, self.n.sz_b() as usize)
+    }
+}
+
+
+/// Paramètres de génération d'une clé RSA, à enchaîner avant `build` :
+/// `KeyGenBuilder::new().bits(1024).public_exponent(BigUint::from(3u8)).build()`.
+/// Les valeurs par défaut sont celles de `KeyPair::generate` : module de `KEYGEN_BITS_DEF` bits (au moins `KEYGEN_MIN_BITS_DEF`),
+/// exposant 65537, deux nombres premiers, 20 itérations du test de Miller-Rabin, au plus `KEYGEN_ATTEMPTS_PER_BIT_DEF` candidats
+/// par bit de module pour chaque nombre premier et générateur `OsRng`.
+pub struct KeyGenBuilder<R = rand::rngs::OsRng>
+{
+    bits: u64,
+    e: BigUint,
+    primes: usize,
+    rounds: u32,
+    min_bits: u64,
+    max_attempts: Option<u64>,
+    rng: R
+}
+
+impl KeyGenBuilder
+{
+    /// Paramètres par défaut (voir `KeyGenBuilder`).
+    pub fn new() -> Self
+    {
+        KeyGenBuilder
+        {
+            bits: KEYGEN_BITS_DEF,
+            e: BigUint::from(PUB_EXP_DEF),
+            primes: 2,
+            rounds: maths::PRIME_ROUNDS_DEF,
+            min_bits: KEYGEN_MIN_BITS_DEF,
+            max_attempts: None,
+            rng: rand::rngs::OsRng
+        }
+    }
+}
+
+impl Default for KeyGenBuilder
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<R: Rng + CryptoRng> KeyGenBuilder<R>
+{
+    /// Taille du module en bits (voir `KeyPair::generate_with` et `KeyPair::generate_multiprime`).
+    pub fn bits(mut self, bits: u64) -> Self
+    {
+        self.bits = bits;
+        self
+    }
+
+    /// Exposant public préféré, qui doit être impair et supérieur à 1. S'il n'est pas premier avec l'indicatrice des nombres premiers
+    /// tirés, les exposants 3, 5, 17, 257 et 65537 qui lui sont supérieurs sont essayés dans cet ordre ; si aucun ne convient,
+    /// de nouveaux nombres premiers sont tirés. L'exposant retenu est celui de la clé (`KeyPair::e`).
+    pub fn public_exponent(mut self, e: BigUint) -> Self
+    {
+        self.e = e;
+        self
+    }
+
+    /// Nombre de facteurs premiers du module (au moins 2) ; au-delà de deux, voir `KeyPair::generate_multiprime`.
+    pub fn primes(mut self, primes: usize) -> Self
+    {
+        self.primes = primes;
+        self
+    }
+
+    /// Nombre d'itérations du test de Miller-Rabin sur chaque candidat.
+    pub fn mr_rounds(mut self, rounds: u32) -> Self
+    {
+        self.rounds = rounds;
+        self
+    }
+
+    /// Taille minimale du module en bits (`KEYGEN_MIN_BITS_DEF` par défaut) : en dessous, `build` retourne `KeyError::KeySizeTooSmall`.
+    pub fn min_bits(mut self, min_bits: u64) -> Self
+    {
+        self.min_bits = min_bits;
+        self
+    }
+
+    /// Lève (`true`) ou rétablit (`false`) la taille minimale du module, par exemple pour générer rapidement de petites clés de test.
+    pub fn allow_small(self, allow: bool) -> Self
+    {
+        self.min_bits(if allow { 0 } else { KEYGEN_MIN_BITS_DEF })
+    }
+
+    /// Nombre maximal de candidats tirés pour chaque nombre premier (`KEYGEN_ATTEMPTS_PER_BIT_DEF` par bit de module par défaut) :
+    /// au-delà, `build` retourne `KeyError::ExhaustedAttempts` plutôt que de chercher indéfiniment avec un générateur défaillant.
+    pub fn max_attempts(mut self, max_attempts: u64) -> Self
+    {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Générateur (cryptographiquement sûr) dont sont tirés les nombres premiers.
+    pub fn rng<R2: Rng + CryptoRng>(self, rng: R2) -> KeyGenBuilder<R2>
+    {
+        KeyGenBuilder { bits: self.bits, e: self.e, primes: self.primes, rounds: self.rounds, min_bits: self.min_bits, max_attempts: self.max_attempts, rng }
+    }
+
+    /// Génère la clé. Retourne `KeyError::InvalidExponent` si l'exposant public est pair ou inférieur à 2,
+    /// `KeyError::InvalidPrimeCount` si moins de deux nombres premiers sont demandés, `KeyError::KeySizeTooSmall`
+    /// si la taille demandée est inférieure au minimum, et `KeyError::ExhaustedAttempts` si le nombre maximal de candidats est atteint.
+    /// Une clé de moins de `KEY_BITS_RECOMMENDED` bits n'est signalée qu'avec la fonctionnalité `logging` (voir `KeyPair::generate`) :
+    /// vérifier `KeyPair::is_weak` sur la clé obtenue sinon.
+    pub fn build(mut self) -> Result<KeyPair, KeyError>
+    {
+        if self.bits < self.min_bits
+        {
+            return Err(KeyError::KeySizeTooSmall);
+        }
+        warn_if_weak(self.bits);
+        if !self.e.bit(0) || self.e <= BigUint::one()
+        {
+            return Err(KeyError::InvalidExponent);
+        }
+        if self.primes < 2
+        {
+            return Err(KeyError::InvalidPrimeCount);
+        }
+
+        let max_attempts = self.max_attempts.unwrap_or_else(|| default_max_attempts(self.bits));
+        if self.primes > 2
+        {
+            return KeyPair::generate_multiprime_with(&mut self.rng, self.bits, self.primes, &self.e, self.rounds, max_attempts);
+        }
+
+        KeyPair::generate_capped(&mut self.rng, self.bits, &self.e, self.rounds, max_attempts)
+    }
+}
+
+
+/// Clé publique RSA seule (`n`, `e`), à distribuer : contrairement à `KeyPair`, elle ne possède aucun champ pour l'exposant privé.
+/// Elle ne permet que le chiffrement et la vérification de signatures ; le déchiffrement et la signature n'existent que sur `KeyPair`,
+/// si bien qu'une opération privée sur une clé publique est refusée à la compilation.
+///
+/// ```compile_fail
+/// let k = rrsa::rsa::KeyPair::generate(512).unwrap();
+/// let public = k.public_key();
+///
+/// public.decrypt(&public.encrypt(&42u32.into()));
+/// ```
+///
+/// ```compile_fail
+/// let k = rrsa::rsa::KeyPair::generate(512).unwrap();
+/// let public = k.public_key();
+///
+/// public.sign(b"abc", &rrsa::hash::Sha256);
+/// ```
+///
+/// Deux clés publiques sont égales (et de même haché) si et seulement si elles ont le même module et le même exposant, donc la même empreinte.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PublicKey
+{
+    /// Module RSA.
+    pub n: BigUint,
+    /// Exposant public.
+    pub e: BigUint
+}
+
+impl PublicKey
+{
+    /// Taille en octets des blocs de clair (voir `KeyPair::block_size`).
+    pub fn block_size(&self) -> u32
+    {
+        self.n.sz_b() - 1
+    }
+
+    /// Chiffre un nombre de taille quelconque : il est découpé en blocs de `block_size` octets, chacun étant chiffré avec la clé publique.
+    /// Les blocs sont déchiffrés par `KeyPair::decrypt`. Panique si le module est inférieur à 256 (`block_size` nul), ce que refusent
+    /// les constructeurs et imports de clés (`KeyError::ModulusTooSmall`) : seule une clé construite champ par champ peut l'atteindre.
+    pub fn encrypt(&self, data: &BigUint) -> Vec<BigUint>
+    {
+        data.expl_r(self.block_size()).iter().map(| block | {
+            maths::fmodpow(block, &self.e, &self.n)
+        }).collect()
+    }
+
+    /// Vérifie une signature RSASSA-PKCS1-v1_5 de `msg` par l'algorithme de hachage `alg` (voir `verify`).
+    pub fn verify(&self, msg: &[u8], sig: &[u8], alg: &dyn DigestAlgorithm) -> bool
+    {
+        verify(&self.n, &self.e, msg, sig, alg)
+    }
+
+    /// Vérifie une signature RSASSA-PSS de l'empreinte SHA-256 `mhash` avec un sel de `salt_len` octets (voir `verify_pss`).
+    pub fn verify_pss(&self, mhash: &[u8], sig: &[u8], salt_len: usize) -> bool
+    {
+        verify_pss(&self.n, &self.e, mhash, sig, salt_len)
+    }
+
+    /// Empreinte courte de la clé, pour l'identifier dans un journal ou une interface : les `FINGERPRINT_LEN` premiers octets
+    /// du SHA-256 de son encodage DER PKCS#1 (`RSAPublicKey`), en hexadécimal séparé par des deux-points (à la manière de SSH).
+    pub fn fingerprint(&self) -> String
+    {
+        let der = der::encode_sequence(&[der::encode_integer(&self.n), der::encode_integer(&self.e)]);
+
+        hash::sha256(&der)[..FINGERPRINT_LEN].iter().map(| byte | format!("{:02x}", byte)).collect::<Vec<_>>().join(":")
+    }
+}
+
+
+/// Affiche la taille du module suivie de l'empreinte de la clé (voir `PublicKey::fingerprint`).
+impl Display for PublicKey
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "RSA-{} {}", self.n.bits(), self.fingerprint())
+    }
+}
+
+
+/// Vérifie une signature RSASSA-PKCS1-v1_5 de `msg` par l'algorithme de hachage `alg` avec la clé publique (`n`, `e`).
+/// La structure DigestInfo contenue dans la signature (OID et empreinte) est comparée en temps constant à celle attendue.
+pub fn verify(n: &BigUint, e: &BigUint, msg: &[u8], sig: &[u8], alg: &dyn DigestAlgorithm) -> bool
+{
+    let k = n.sz_b() as usize;
+    let s = BigUint::from_bytes_be(sig);
+    if sig.len() != k || &s >= n
+    {
+        return false;
+    }
+
+    let em = messages::biguint_to_bytes(&maths::fmodpow(&s, e, n), k);
+    match pkcs1::unpad_pkcs1_sign(&em)
+    {
+        Ok(info) => pkcs1::ct_eq(&info, &pkcs1::digest_info(&alg.digest(msg), alg.oid())),
+        Err(_) => false
+    }
+}
+
+/// Vérifie une signature RSASSA-PSS de l'empreinte SHA-256 `mhash` avec la clé publique (`n`, `e`) et un sel de `salt_len` octets.
+pub fn verify_pss(n: &BigUint, e: &BigUint, mhash: &[u8], sig: &[u8], salt_len: usize) -> bool
+{
+    let k = n.sz_b() as usize;
+    let s = BigUint::from_bytes_be(sig);
+    if sig.len() != k || &s >= n
+    {
+        return false;
+    }
+
+    // L'encodage fait modBits - 1 bits : il tient sur un octet de moins que le module lorsque modBits - 1 est multiple de 8
+    let em_bits = n.bits() as usize - 1;
+    let em = messages::biguint_to_bytes(&maths::fmodpow(&s, e, n), k);
+    let (head, em) = em.split_at(k - em_bits.div_ceil(8));
+
+    head.iter().all(| &b | b == 0x00) && pkcs1::emsa_pss_verify(mhash, em, em_bits, salt_len)
+}
+
+/// Inverse modulaire sur les grands entiers positifs (voir `maths::mod_inverse`).
+fn inverse(a: &BigUint, modulus: &BigUint) -> Option<BigUint>
+{
+    maths::mod_inverse(&a.to_bigint().unwrap(), &modulus.to_bigint().unwrap()).map(| x | x.to_biguint().unwrap())
+}
+
+/// Retourne `a` si `cond` est vrai, `b` sinon, sans branchement sur `cond` : les chiffres des deux valeurs, complétés à la même longueur,
+/// sont combinés par masque. Les opérations de `num_bigint` n'étant pas elles-mêmes à temps constant, il s'agit d'une atténuation.
+pub(crate) fn ct_select(cond: bool, a: &BigUint, b: &BigUint) -> BigUint
+{
+    let (a, b) = (a.to_u32_digits(), b.to_u32_digits());
+    let mask = 0u32.wrapping_sub(u32::from(cond));
+    let digit = | digits: &[u32], i: usize | digits.get(i).copied().unwrap_or(0);
+
+    BigUint::new((0..a.len().max(b.len())).map(| i | (digit(&a, i) & mask) | (digit(&b, i) & !mask)).collect())
+}
+
+/// Choisit l'exposant public d'une clé d'indicatrice `phi` : l'exposant demandé `preferred` s'il est premier avec `phi`, sinon le premier
+/// exposant de `PUB_EXP_FALLBACK` (3, 5, 17, 257 puis 65537) qui lui est supérieur et premier avec `phi`. Retourne `None` si aucun ne convient
+/// (notamment si 65537 a été demandé et divise `phi`) : de nouveaux nombres premiers doivent alors être tirés.
+pub(crate) fn choose_exponent(preferred: &BigUint, phi: &BigUint) -> Option<BigUint>
+{
+    let fallback = PUB_EXP_FALLBACK.iter().map(| &e | BigUint::from(e)).filter(| e | e > preferred);
+
+    std::iter::once(preferred.clone()).chain(fallback).find(| e | maths::gcd(e, phi).is_one())
+}
+
+/// Indique si deux nombres premiers de `prime_bits` bits sont trop proches : `|p - q| < 2^(prime_bits - PRIME_DISTANCE_MARGIN)`.
+/// Deux nombres premiers égaux sont toujours trop proches.
+fn primes_too_close(p: &BigUint, q: &BigUint, prime_bits: u64) -> bool
+{
+    let distance = if p > q { p - q } else { q - p };
+
+    distance.bits() <= prime_bits.saturating_sub(PRIME_DISTANCE_MARGIN)
+}
+
+/// Retourne un nombre premier (probable) de exactement `bits` bits, en tirant des candidats jusqu'à en trouver un.
+/// Les candidats divisibles par l'un des petits nombres premiers `sieve` sont écartés sans test de Miller-Rabin ; les autres le subissent avec `rounds` itérations.
+/// Chaque candidat testé est signalé à `progress`. Retourne `None` si aucun des `max_attempts` premiers candidats n'est premier.
+pub(crate) fn rand_prime<R>(rng: &mut R, bits: u64, sieve: &[u32], rounds: u32, max_attempts: u64, progress: &mut dyn FnMut(KeygenEvent)) -> Option<BigUint>
+where R: Rng + CryptoRng
+{
+    rand_prime_cancellable(rng, bits, sieve, rounds, max_attempts, progress, &AtomicBool::new(false))
+}
+
+/// Voir `rand_prime`, la recherche s'arrêtant aussi (`None`) si le drapeau `cancel` est levé avant le tirage d'un candidat.
+fn rand_prime_cancellable<R>(rng: &mut R, bits: u64, sieve: &[u32], rounds: u32, max_attempts: u64, progress: &mut dyn FnMut(KeygenEvent),
+    cancel: &AtomicBool) -> Option<BigUint>
+where R: Rng + CryptoRng
+{
+    let szb = bits.div_ceil(8);
+    let (start, mut tested) = (Instant::now(), 0u64);
+
+    while tested < max_attempts && !cancel.load(Ordering::Relaxed)
+    {
+        // Les bits excédentaires sont retirés par la droite : les deux bits de poids fort restent à 1
+        let mut candidate = maths::rand_primelike(rng, szb) >> (szb * 8 - bits);
+        candidate.set_bit(0, true);
+        let prime = !maths::divisible_by_small(&candidate, sieve) && maths::is_prime_rounds(&candidate, rounds);
+        progress(KeygenEvent::CandidateTested);
+        tested += 1;
+        logging::emit(Level::Trace, || format!("candidat de {} bits testé : {}", bits, if prime { "premier" } else { "composé" }));
+        if prime
+        {
+            logging::emit(Level::Debug, || format!("nombre premier de {} bits trouvé en {:?} ({} candidats)", bits, start.elapsed(), tested));
+            return Some(candidate);
+        }
+    }
+
+    None
+}
+
+/// Voir `rand_prime` : les candidats sont tirés et testés par `threads` threads, chacun avec son propre générateur.
+/// Le premier thread qui trouve un nombre premier lève le drapeau `found`, que les autres consultent entre deux candidats pour s'arrêter.
+/// Les threads s'arrêtent aussi (`None`) lorsqu'ils ont tiré `max_attempts` candidats au total.
+#[cfg(feature = "parallel")]
+fn rand_prime_parallel(bits: u64, sieve: &[u32], rounds: u32, threads: usize, max_attempts: u64) -> Option<BigUint>
+{
+    use rand::{rngs::{OsRng, StdRng}, SeedableRng};
+    use std::sync::{atomic::AtomicU64, mpsc};
+
+    let szb = bits.div_ceil(8);
+    let (found, tested) = (AtomicBool::new(false), AtomicU64::new(0));
+    let (tx, rx) = mpsc::channel();
+
+    crossbeam::scope(| s |
+    {
+        for _ in 0..threads
+        {
+            let (tx, found, tested) = (tx.clone(), &found, &tested);
+            s.spawn(move | _ |
+            {
+                // L'entropie du système ne manque pas : l'initialisation ne peut échouer qu'en cas d'erreur grave de l'OS
+                let mut rng = StdRng::from_rng(OsRng).expect("rand_prime_parallel : initialisation du générateur impossible");
+
+                while !found.load(Ordering::Relaxed) && tested.fetch_add(1, Ordering::Relaxed) < max_attempts
+                {
+                    let mut candidate = maths::rand_primelike(&mut rng, szb) >> (szb * 8 - bits);
+                    candidate.set_bit(0, true);
+                    if !maths::divisible_by_small(&candidate, sieve) && maths::is_prime_rounds(&candidate, rounds)
+                    {
+                        found.store(true, Ordering::Relaxed);
+                        // Le récepteur vit jusqu'à la fin de la portée : l'envoi ne peut pas échouer
+                        let _ = tx.send(candidate);
+                    }
+                }
+            });
+        }
+    }).expect("rand_prime_parallel : un thread de recherche a paniqué");
+
+    // Plusieurs threads peuvent trouver un nombre premier au même moment : seul le premier reçu est retenu.
+    // Tous les threads sont terminés : le canal est vide si aucun n'a trouvé de nombre premier
+    rx.try_recv().ok()
+}
+
+/// Refuse (`KeyError::ModulusTooSmall`) un module inférieur à 256, qui ne tient que sur un octet : les blocs de clair, plus courts
+/// d'un octet que le module (`block_size`), seraient vides.
+pub(crate) fn check_modulus(n: &BigUint) -> Result<(), KeyError>
+{
+    if n.bits() <= 8
+    {
+        return Err(KeyError::ModulusTooSmall);
+    }
+
+    Ok(())
+}
+
+/// Refuse (`KeyError::InvalidExponent`) un exposant public pair, inférieur ou égal à 1, ou supérieur ou égal au module `n`.
+pub(crate) fn check_exponent(e: &BigUint, n: &BigUint) -> Result<(), KeyError>
+{
+    if !e.bit(0) || e <= &BigUint::one() || e >= n
+    {
+        return Err(KeyError::InvalidExponent);
+    }
+
+    Ok(())
+}
+
+/// Refuse (`KeyError::KeySizeTooSmall`) une taille de module inférieure à `KEYGEN_MIN_BITS_DEF` bits pour les générateurs de `KeyPair`,
+/// et signale la génération d'une clé faible (voir `warn_if_weak`).
+fn check_key_size(bits: u64) -> Result<(), KeyError>
+{
+    if bits < KEYGEN_MIN_BITS_DEF
+    {
+        return Err(KeyError::KeySizeTooSmall);
+    }
+    warn_if_weak(bits);
+
+    Ok(())
+}
+
+/// Émet un avertissement pour la génération d'une clé de moins de `KEY_BITS_RECOMMENDED` bits. Sans la fonctionnalité `logging`,
+/// l'avertissement est ignoré (voir `logging::emit`) : `KeyPair::is_weak` reste le seul moyen de détecter une clé faible.
+fn warn_if_weak(bits: u64)
+{
+    if bits < KEY_BITS_RECOMMENDED
+    {
+        logging::emit(Level::Warn, || format!("génération d'une clé faible de {} bits (moins de {})", bits, KEY_BITS_RECOMMENDED));
+    }
+}
+
+/// Nombre maximal de candidats tirés par défaut pour chaque nombre premier d'une clé de `bits` bits (voir `KEYGEN_ATTEMPTS_PER_BIT_DEF`).
+fn default_max_attempts(bits: u64) -> u64
+{
+    bits.saturating_mul(KEYGEN_ATTEMPTS_PER_BIT_DEF)
+}
\ No newline at end of file
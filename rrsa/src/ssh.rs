@@ -0,0 +1,43 @@
+use crate::{base64, rsa::PublicKey};
+use num_bigint::BigUint;
+
+
+/// Nom de l'algorithme d'une clé RSA dans le format OpenSSH.
+const SSH_RSA: &str = "ssh-rsa";
+
+
+impl PublicKey
+{
+    /// Exporte la clé au format de clé publique OpenSSH (ligne `ssh-rsa AAAA...` d'un fichier `authorized_keys`) :
+    /// base64 de la concaténation de la chaîne `"ssh-rsa"`, de `e` puis de `n` (RFC 4253, section 6.6), chacun précédé de sa longueur.
+    pub fn to_openssh(&self) -> String
+    {
+        let mut blob = Vec::new();
+        put_string(&mut blob, SSH_RSA.as_bytes());
+        put_mpint(&mut blob, &self.e);
+        put_mpint(&mut blob, &self.n);
+
+        format!("{} {}", SSH_RSA, base64::base64_encode(&blob))
+    }
+}
+
+
+/// Écrit une chaîne SSH : sa longueur sur 4 octets big-endian, suivie de son contenu.
+fn put_string(out: &mut Vec<u8>, data: &[u8])
+{
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(data);
+}
+
+/// Écrit un entier `mpint` SSH : ses octets big-endian minimaux, précédés d'un octet nul si le bit de poids fort est à 1
+/// (l'entier serait sinon lu comme négatif). Zéro est encodé par une chaîne vide.
+fn put_mpint(out: &mut Vec<u8>, n: &BigUint)
+{
+    let mut bytes = if n.bits() == 0 { Vec::new() } else { n.to_bytes_be() };
+    if bytes.first().is_some_and(| &b | b & 0x80 != 0)
+    {
+        bytes.insert(0, 0);
+    }
+
+    put_string(out, &bytes);
+}
\ No newline at end of file
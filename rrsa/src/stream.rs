@@ -0,0 +1,92 @@
+use crate::{maths::{self, NumUtil}, messages, pkcs1, rsa::KeyPair};
+use std::io::{self, Read, Write};
+
+
+/// Chiffre un flux de taille quelconque sans le charger entièrement en mémoire : l'entrée est lue par blocs de `n.sz_b() - 11` octets,
+/// chacun recevant le padding de chiffrement PKCS#1 v1.5 avant d'être chiffré avec la clé publique.
+/// Chaque bloc chiffré est écrit sur exactement `n.sz_b()` octets ; le dernier bloc de clair peut être plus court.
+pub fn encrypt_reader<R: Read, W: Write>(key: &KeyPair, mut input: R, mut output: W) -> io::Result<()>
+{
+    let k = key.n.sz_b() as usize;
+    let chunk_len = k.checked_sub(pkcs1::PKCS1_ENCRYPT_OVERHEAD).filter(| &len | len > 0)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "encrypt_reader : module trop petit pour le padding PKCS#1"))?;
+    let mut chunk = vec![0u8; chunk_len];
+    let mut rng = rand::rngs::OsRng;
+
+    loop
+    {
+        let len = read_full(&mut input, &mut chunk)?;
+        if len == 0
+        {
+            break;
+        }
+
+        let em = pkcs1::pad_pkcs1_encrypt(&chunk[..len], k, &mut rng).map_err(invalid)?;
+        let c = maths::fmodpow(&messages::bytes_to_biguint(&em), &key.e, &key.n);
+        output.write_all(&messages::biguint_to_bytes(&c, k))?;
+
+        if len < chunk_len
+        {
+            break;
+        }
+    }
+
+    output.flush()
+}
+
+/// Déchiffre un flux produit par `encrypt_reader` : l'entrée est lue par blocs de `n.sz_b()` octets, chacun étant déchiffré
+/// avec la clé privée puis débarrassé de son padding. Retourne une erreur `InvalidData` si un bloc est tronqué ou invalide.
+pub fn decrypt_reader<R: Read, W: Write>(key: &KeyPair, mut input: R, mut output: W) -> io::Result<()>
+{
+    let k = key.n.sz_b() as usize;
+    let mut block = vec![0u8; k];
+
+    loop
+    {
+        let len = read_full(&mut input, &mut block)?;
+        if len == 0
+        {
+            break;
+        }
+        if len < k
+        {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "decrypt_reader : bloc chiffré tronqué"));
+        }
+
+        let c = messages::bytes_to_biguint(&block);
+        if c >= key.n
+        {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "decrypt_reader : bloc chiffré supérieur au module"));
+        }
+        let em = messages::biguint_to_bytes(&key.decrypt_crt(&c), k);
+        output.write_all(&pkcs1::unpad_pkcs1_decrypt(&em).map_err(invalid)?)?;
+    }
+
+    output.flush()
+}
+
+
+/// Lit jusqu'à remplir `buf` ou atteindre la fin du flux, et retourne le nombre d'octets lus.
+fn read_full<R: Read>(input: &mut R, buf: &mut [u8]) -> io::Result<usize>
+{
+    let mut len = 0;
+
+    while len < buf.len()
+    {
+        match input.read(&mut buf[len..])
+        {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e)
+        }
+    }
+
+    Ok(len)
+}
+
+/// Convertit une erreur de padding en erreur d'entrée / sortie `InvalidData`.
+fn invalid(e: pkcs1::PadError) -> io::Error
+{
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
\ No newline at end of file
@@ -0,0 +1,101 @@
+use num_bigint::BigUint;
+use alloc::vec::Vec;
+use core::{hint, ops::{Deref, DerefMut}, ptr};
+
+
+/// Effacement (mise à zéro) en place de données secrètes.
+/// Les écritures sont volatiles afin que le compilateur ne puisse pas les supprimer, même si la valeur n'est plus lue ensuite.
+pub trait Zeroize
+{
+    /// Écrase le contenu de la valeur par des zéros.
+    fn zeroize(&mut self);
+}
+
+impl Zeroize for [u8]
+{
+    fn zeroize(&mut self)
+    {
+        for byte in self.iter_mut()
+        {
+            // SAFETY : `byte` est une référence mutable valide et alignée
+            unsafe { ptr::write_volatile(byte, 0) };
+        }
+        hint::black_box(&*self);
+    }
+}
+
+impl<const N: usize> Zeroize for [u8; N]
+{
+    fn zeroize(&mut self)
+    {
+        self[..].zeroize();
+    }
+}
+
+impl Zeroize for Vec<u8>
+{
+    /// Efface toute la capacité allouée (et pas seulement les octets utilisés), puis vide le vecteur.
+    fn zeroize(&mut self)
+    {
+        self.resize(self.capacity(), 0);
+        self[..].zeroize();
+        self.clear();
+    }
+}
+
+impl Zeroize for BigUint
+{
+    /// `BigUint` n'exposant pas ses limbes, les bits à 1 sont remis à zéro un à un, du poids faible au poids fort :
+    /// les limbes sont ainsi écrasées en place avant que le tampon ne soit libéré.
+    /// Les copies intermédiaires produites par les calculs (temporaires de `fmodpow`, etc) ne sont pas concernées.
+    fn zeroize(&mut self)
+    {
+        for bit in 0..self.bits()
+        {
+            if self.bit(bit)
+            {
+                self.set_bit(bit, false);
+            }
+        }
+        hint::black_box(&*self);
+    }
+}
+
+
+/// Enveloppe d'une valeur secrète, effacée automatiquement lorsqu'elle est libérée.
+pub struct Zeroizing<T: Zeroize>(T);
+
+impl<T: Zeroize> Zeroizing<T>
+{
+    /// Enveloppe une valeur secrète.
+    pub fn new(value: T) -> Self
+    {
+        Zeroizing(value)
+    }
+}
+
+impl<T: Zeroize> Deref for Zeroizing<T>
+{
+    type Target = T;
+
+    fn deref(&self) -> &T
+    {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> DerefMut for Zeroizing<T>
+{
+    fn deref_mut(&mut self) -> &mut T
+    {
+        &mut self.0
+    }
+}
+
+impl<T: Zeroize> Drop for Zeroizing<T>
+{
+    fn drop(&mut self)
+    {
+        self.0.zeroize();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,25 @@
+//! Test de la frontière publique du module `maths` : compilé comme une crate externe, il n'a accès qu'aux éléments publics.
+//! Les réglages internes (`PRIME_ROUNDS_DEF`, `SIEVE_LIMIT_DEF`, `EXPCODE_TAB`, etc) ne peuvent pas être importés ici.
+
+use num_bigint::{BigInt, BigUint};
+use rrsa::maths::{euclide, fmodpow, isprime, rand_primelike, NumUtil, VecNumUtil};
+
+
+/// Test des primitives publiques : découpage / recomposition, exponentiation, PGCD, primalité et tirage d'un candidat
+#[test]
+fn public_primitives()
+{
+    let n = BigUint::from(0x0102_0304_0506u64);
+    let blocks = n.expl_r(2);
+    assert_eq!(n, blocks.rejoin_fixed(2));
+
+    assert_eq!(BigUint::from(1u8), fmodpow(&BigUint::from(3u8), &BigUint::from(4u8), &BigUint::from(10u8)));
+    // Coefficient de Bézout u de 48 : 48 u ≡ pgcd(48, 18) = 6 (mod 18)
+    let u = euclide(&BigInt::from(48), &BigInt::from(18));
+    assert_eq!(BigInt::from(0), (BigInt::from(48) * u - 6) % 18);
+    assert!(isprime(&BigUint::from(65537u32)));
+
+    let candidate = rand_primelike(&mut rand::rngs::OsRng, 16);
+    assert_eq!(128, candidate.bits());
+    assert!(candidate.bit(0));
+}
\ No newline at end of file
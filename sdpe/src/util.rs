@@ -167,14 +167,14 @@ impl GenEngine
                             match kt.as_str()
                             {
                                 "main" | "pair" => {
-                                    let rk = RsaKey::from_str(String::from(rk)).expect("-> Impossible de charger la clé.");
+                                    let rk = RsaKey::from_str(rk).expect("-> Impossible de charger la clé.");
                                     (Some(rk.0), Some(rk.1))
                                 },
                                 "public" | "publ" => {
-                                    (Some(PublicKey::from_str(String::from(rk)).expect("-> Impossible de charger la clé.")), None)
+                                    (Some(PublicKey::from_str(rk).expect("-> Impossible de charger la clé.")), None)
                                 },
                                 "private" | "priv" => {
-                                    (None, Some(PrivateKey::from_str(String::from(rk)).expect("-> Impossible de charger la clé.")))
+                                    (None, Some(PrivateKey::from_str(rk).expect("-> Impossible de charger la clé.")))
                                 },
                                 _ => {
                                     eprintln!("-> Type de clé invalide : {}.", kt);
@@ -242,7 +242,7 @@ impl GenEngine
                     "export" => {
                         let kt = args.value_of("keytype").unwrap().to_lowercase();
                         let rk = read_to_string(kpath).expect("-> Impossible de lire le fichier clé.");
-                        let rk = RsaKey::from_str(String::from(rk)).expect("-> Impossible de charger la clé.");
+                        let rk = RsaKey::from_str(rk).expect("-> Impossible de charger la clé.");
 
                         if !args.is_present("output")
                         {